@@ -0,0 +1,241 @@
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// the subset of toml oneless reads: `[sections]`, dotted keys, strings,
+// integers and single line arrays of strings, e.g.
+//
+//   reserve = 3
+//
+//   [format.git_log]
+//   prioritizer = "head_and_tail"
+//   keep = ['^commit ', "Merge"]
+//
+// or `format.git_log.keep = [...]` at the top level. formats are named after
+// the prioritizer that detects them. prioritizers take options as strings,
+// and as with `-p`, `path-depth` is `path_depth`:
+//
+//   [prioritizer.head_and_tail]
+//   head = "70%"
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormatConfig {
+    // prioritizer used instead of the one that detected the format
+    pub prioritizer: Option<String>,
+    // regexes of lines to keep, or to give up, before any other
+    pub keep: Vec<String>,
+    pub drop: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    // terminal lines left free below the output, for the prompt
+    pub reserve: Option<usize>,
+    pub formats: HashMap<String, FormatConfig>,
+    // options by prioritizer name, then option name
+    pub prioritizers: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Str(String),
+    Integer(usize),
+    Array(Vec<String>),
+}
+
+// a `"basic"` or `'literal'` string at the start of `s`, and what follows it
+fn string(s: &str) -> Result<(String, &str)> {
+    let mut chars = s.char_indices();
+    let quote = match chars.next() {
+        Some((_, q)) if q == '"' || q == '\'' => q,
+        _ => bail!("expected a string, found {}", s),
+    };
+    let mut value = String::new();
+    let mut escaped = false;
+    for (i, c) in chars {
+        if escaped {
+            value.push(match c {
+                'n' => '\n',
+                't' => '\t',
+                c => c,
+            });
+            escaped = false;
+        } else if c == '\\' && quote == '"' {
+            escaped = true;
+        } else if c == quote {
+            return Ok((value, &s[i + 1..]));
+        } else {
+            value.push(c);
+        }
+    }
+    bail!("unterminated string: {}", s)
+}
+
+fn value(s: &str) -> Result<Value> {
+    let s = s.trim();
+    let number = s.split('#').next().unwrap_or("").trim();
+    if let Ok(n) = number.parse() {
+        return Ok(Value::Integer(n));
+    }
+    let Some(mut rest) = s.strip_prefix('[') else {
+        let (value, rest) = string(s)?;
+        if !rest.trim().is_empty() && !rest.trim().starts_with('#') {
+            bail!("unexpected {} after string", rest.trim());
+        }
+        return Ok(Value::Str(value));
+    };
+    let mut values = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(']') {
+            if !after.trim().is_empty() && !after.trim().starts_with('#') {
+                bail!("unexpected {} after array", after.trim());
+            }
+            return Ok(Value::Array(values));
+        }
+        let (value, after) = string(rest)?;
+        values.push(value);
+        rest = after.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+}
+
+impl Config {
+    pub fn parse(text: &str) -> Result<Config> {
+        let mut config = Config::default();
+        let mut section = String::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = format!("{}.", name.trim());
+                continue;
+            }
+            let Some((key, raw_value)) = line.split_once('=') else {
+                bail!("line {}: expected `key = value`", n + 1);
+            };
+            let key = format!("{}{}", section, key.trim());
+            let value = value(raw_value).with_context(|| format!("line {}", n + 1))?;
+            if key == "reserve" {
+                let Value::Integer(reserve) = value else {
+                    bail!("line {}: reserve takes a number of lines", n + 1);
+                };
+                config.reserve = Some(reserve);
+                continue;
+            }
+            if let Some((prioritizer, option)) = key
+                .strip_prefix("prioritizer.")
+                .and_then(|k| k.rsplit_once('.'))
+            {
+                let Value::Str(value) = value else {
+                    bail!("line {}: {} takes a string", n + 1, key);
+                };
+                config
+                    .prioritizers
+                    .entry(prioritizer.replace('-', "_"))
+                    .or_default()
+                    .insert(option.to_string(), value);
+                continue;
+            }
+            let Some((format, field)) =
+                key.strip_prefix("format.").and_then(|k| k.rsplit_once('.'))
+            else {
+                bail!("line {}: unknown key {}", n + 1, key);
+            };
+            let format = config.formats.entry(format.to_string()).or_default();
+            match (field, value) {
+                ("prioritizer", Value::Str(name)) => format.prioritizer = Some(name),
+                ("keep", Value::Array(patterns)) => format.keep = patterns,
+                ("drop", Value::Array(patterns)) => format.drop = patterns,
+                _ => bail!("line {}: unknown key or wrong type for {}", n + 1, key),
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn prioritizer_option(&self, prioritizer: &str, option: &str) -> Option<&str> {
+        self.prioritizers
+            .get(prioritizer)?
+            .get(option)
+            .map(String::as_str)
+    }
+
+    // `path` if given, otherwise `$XDG_CONFIG_HOME/oneless/config.toml` or
+    // `~/.config/oneless/config.toml` when it exists
+    pub fn load(path: Option<&str>) -> Result<Config> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let base = std::env::var_os("XDG_CONFIG_HOME")
+                    .map(PathBuf::from)
+                    .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+                match base.map(|b| b.join("oneless").join("config.toml")) {
+                    Some(default) if default.exists() => default,
+                    _ => return Ok(Config::default()),
+                }
+            }
+        };
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("unable to read {}", path.display()))?;
+        Config::parse(&text).with_context(|| format!("invalid config {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn parse_config() -> Result<()> {
+        let config = Config::parse(
+            "# tuning\n\
+             reserve = 3 # two line prompt\n\
+             format.git_log.prioritizer = \"head_and_tail\"\n\
+             \n\
+             [format.log_level]\n\
+             keep = ['^\\+\\+\\+', \"panic\\\\b\"] # trailing comment\n\
+             drop = []\n\
+             [prioritizer.head_and_tail]\n\
+             head = \"70%\"\n",
+        )?;
+        expect_that!(config.reserve, some(eq(3)));
+        expect_that!(
+            config.formats["git_log"].prioritizer,
+            some(eq("head_and_tail"))
+        );
+        expect_that!(
+            config.formats["log_level"].keep,
+            eq(&vec![r"^\+\+\+".to_string(), r"panic\b".to_string()])
+        );
+        expect_that!(config.formats["log_level"].drop, is_empty());
+        expect_that!(
+            config.prioritizer_option("head_and_tail", "head"),
+            some(eq("70%"))
+        );
+        let config = Config::parse("[prioritizer.path-depth]\nsep = \"::\"\n")?;
+        expect_that!(
+            config.prioritizer_option("path_depth", "sep"),
+            some(eq("::"))
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn parse_invalid_config() {
+        expect_that!(Config::parse("format.x.keep = \"a\"").is_err(), eq(true));
+        expect_that!(Config::parse("colour = \"red\"").is_err(), eq(true));
+        expect_that!(
+            Config::parse("format.x.prioritizer = \"a").is_err(),
+            eq(true)
+        );
+        expect_that!(Config::parse("format.x.keep = ['a'").is_err(), eq(true));
+        expect_that!(Config::parse("format.x.keep").is_err(), eq(true));
+        expect_that!(Config::parse("reserve = \"3\"").is_err(), eq(true));
+        expect_that!(Config::parse("format.x.keep = 3").is_err(), eq(true));
+        expect_that!(Config::parse("prioritizer.x.y = ['a']").is_err(), eq(true));
+    }
+}
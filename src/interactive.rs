@@ -0,0 +1,218 @@
+use crate::lines::{LineStatus, Lines};
+use anyhow::Result;
+use crossterm::cursor::{Hide, MoveTo, MoveToColumn, MoveUp, Show};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use std::io::{Write, stdout};
+use std::ops::Range;
+
+// `--interactive`: the condensed view, then on space the next most important
+// of the hidden lines, pruning again to a page more than before. like `more`,
+// but the lines come by priority rather than in order. keys are read from the
+// terminal, stdin being the input. clicking a marker shows the lines it
+// stands for
+
+const HELP: &str = "space: more, click ...: expand, j/k: scroll, q: quit";
+
+// leaves the terminal as it found it, however the pager exits. without the
+// alternate screen the view is drawn below the prompt, and stays in the
+// scrollback once the pager exits
+struct Screen {
+    alternate: bool,
+    // rows drawn above the status line, to go back over on the next draw
+    drawn: u16,
+}
+impl Screen {
+    fn enter(alternate: bool) -> Result<Screen> {
+        terminal::enable_raw_mode()?;
+        if alternate {
+            execute!(stdout(), EnterAlternateScreen)?;
+        }
+        execute!(stdout(), Hide, EnableMouseCapture)?;
+        Ok(Screen {
+            alternate,
+            drawn: 0,
+        })
+    }
+
+    // the view fills the screen, the status line being on the last row, so
+    // that rows on screen are rows of the view in either mode
+    fn draw(
+        &mut self,
+        rows: &[(usize, String)],
+        scroll: usize,
+        page: usize,
+        total: usize,
+    ) -> Result<()> {
+        let mut out = stdout();
+        if self.alternate {
+            queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        } else {
+            queue!(out, MoveToColumn(0))?;
+            if self.drawn > 0 {
+                queue!(out, MoveUp(self.drawn))?;
+            }
+            queue!(out, Clear(ClearType::FromCursorDown))?;
+        }
+        let view = rows.iter().skip(scroll).map(|r| r.1.as_str());
+        for text in view.chain(std::iter::repeat("")).take(page) {
+            queue!(out, Print(text), Print("\r\n"))?;
+        }
+        let status = if rows.len() < total {
+            format!("-- {} of {} lines, {} --", rows.len(), total, HELP)
+        } else {
+            format!("-- all {} lines, {} --", total, HELP)
+        };
+        queue!(
+            out,
+            SetAttribute(Attribute::Reverse),
+            Print(status),
+            SetAttribute(Attribute::Reset)
+        )?;
+        out.flush()?;
+        self.drawn = page as u16;
+        Ok(())
+    }
+}
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let mut out = stdout();
+        if self.alternate {
+            let _ = execute!(out, LeaveAlternateScreen);
+        } else {
+            // the view stays, the status line goes
+            let _ = execute!(out, MoveToColumn(0), Clear(ClearType::CurrentLine));
+        }
+        let _ = execute!(out, DisableMouseCapture, Show);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// `lines`, prioritized but not pruned yet, pruned to `budget` lines. the
+// lines of expanded blocks go before any other
+fn condensed(lines: &Lines, budget: usize, expanded: &[Range<usize>]) -> Lines {
+    let mut lines = lines.clone();
+    if !expanded.is_empty() {
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let rank = if expanded.iter().any(|b| b.contains(&line_number)) {
+                0
+            } else {
+                1
+            };
+            line.prio.insert(0, rank);
+        }
+    }
+    lines.target_lines = budget;
+    lines.prune();
+    lines
+}
+
+pub fn run(lines: &Lines, alternate_screen: bool) -> Result<()> {
+    let mut screen = Screen::enter(alternate_screen)?;
+    // the last row is for the status line
+    let mut page = (terminal::size()?.1 as usize).saturating_sub(1).max(1);
+    let mut budget = page;
+    let mut expanded: Vec<Range<usize>> = Vec::new();
+    let mut pruned = condensed(lines, budget, &expanded);
+    let mut rows = pruned.rows();
+    let mut scroll = 0;
+    loop {
+        scroll = scroll.min(rows.len().saturating_sub(page));
+        screen.draw(&rows, scroll, page, lines.lines.len())?;
+        match event::read()? {
+            Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) => match code {
+                KeyCode::Char(' ') if budget < lines.lines.len() => {
+                    budget += page;
+                    pruned = condensed(lines, budget, &expanded);
+                    rows = pruned.rows();
+                }
+                KeyCode::Char('j') | KeyCode::Down => scroll += 1,
+                KeyCode::Char('k') | KeyCode::Up => scroll = scroll.saturating_sub(1),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                _ => (),
+            },
+            Event::Mouse(MouseEvent { kind, row, .. }) => match kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let Some((line_number, _)) = rows.get(scroll + row as usize) else {
+                        continue;
+                    };
+                    if pruned.lines[*line_number].status != LineStatus::DotDotDot {
+                        continue;
+                    }
+                    // the marker's row goes to the first line of the block
+                    let block = pruned.omitted_block(*line_number);
+                    budget += block.len() - 1;
+                    expanded.push(block);
+                    pruned = condensed(lines, budget, &expanded);
+                    rows = pruned.rows();
+                }
+                MouseEventKind::ScrollDown => scroll += 1,
+                MouseEventKind::ScrollUp => scroll = scroll.saturating_sub(1),
+                _ => (),
+            },
+            Event::Resize(_, height) => {
+                page = (height as usize).saturating_sub(1).max(1);
+                if budget < page {
+                    budget = page;
+                    pruned = condensed(lines, budget, &expanded);
+                    rows = pruned.rows();
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn reveal_more_lines() -> Result<()> {
+        let text: String = (0..30).map(|n| format!("{}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 100, 20)?;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(line_number as u32);
+        }
+        let first = condensed(&lines, 10, &[]).rows();
+        let more = condensed(&lines, 20, &[]).rows();
+        expect_that!(first.len(), eq(10));
+        expect_that!(more.len(), eq(20));
+        let kept: Vec<&(usize, String)> = first.iter().filter(|r| !r.1.contains("...")).collect();
+        expect_that!(kept.iter().all(|r| more.contains(r)), eq(true));
+        expect_that!(condensed(&lines, 40, &[]).rows().len(), eq(30));
+        Ok(())
+    }
+
+    #[gtest]
+    fn expand_block() -> Result<()> {
+        let text: String = (0..30).map(|n| format!("{}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 100, 20)?;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(line_number as u32);
+        }
+        let pruned = condensed(&lines, 10, &[]);
+        let block = pruned.omitted_block(9);
+        expect_that!(block, eq(&(9..30)));
+        let expanded = [Range { start: 12, end: 18 }];
+        let rows = condensed(&lines, 10 + 5, &expanded).rows();
+        let shown: Vec<usize> = rows.iter().map(|r| r.0).collect();
+        expect_that!((12..18).all(|n| shown.contains(&n)), eq(true));
+        Ok(())
+    }
+}
@@ -1,8 +1,17 @@
-use anyhow::{Error, Result};
-use std::cmp::Ordering;
+use crate::preprocess;
+use crate::prioritizer::expose_section_marker;
+use anyhow::{Error, Result, bail};
+use std::cmp::{Ordering, Reverse};
 use std::io::{BufRead, Write};
+use std::ops::Range;
+use std::str::FromStr;
 
 const DOTDOTDOT: &str = "...";
+const DIM: &str = "\x1b[0;2m";
+// shortest sample of a line worth showing in a marker
+const MIN_SAMPLE_WIDTH: usize = 4;
+// kinds of lines named in a summary, the others are counted together
+const MAX_SUMMARY_GROUPS: usize = 3;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LineStatus {
@@ -12,11 +21,34 @@ pub enum LineStatus {
     DotDotDot,
 }
 
+// set by the tool producing the input, see preprocess::take_directive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pin {
+    Keep,
+    Drop,
+}
+
 #[derive(Clone, Debug)]
 pub struct Line {
     pub prio: Vec<u32>, // compared left to right, lowest prio = important line
     pub status: LineStatus,
     pub text: String,
+    // an earlier line that stays as long as this one does, e.g. the mapping
+    // key above a nested value
+    pub parent: Option<usize>,
+    // an earlier line this one is kept or dropped with, e.g. the first line
+    // of its record
+    pub head: Option<usize>,
+    pub pin: Option<Pin>,
+    // the line standing for this one, e.g. the first of a cluster of near
+    // duplicates. it says how many it stands for once they are pruned
+    pub similar_to: Option<usize>,
+    // the line before it was cut to the terminal width, annotations
+    // included, see preprocess::shorten_cut_lines
+    pub uncut: Option<String>,
+    // number of the input line this one was read from, from 0. folding
+    // duplicates or progress leaves gaps
+    pub source: usize,
 }
 
 impl Line {
@@ -25,8 +57,27 @@ impl Line {
             prio: Vec::new(),
             status: LineStatus::Kept,
             text: s.chars().take(len).collect(),
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: (s.chars().count() > len).then(|| s.to_string()),
+            source: 0,
         }
     }
+
+    // adds an annotation such as ` (×3)`, cutting the text short if the
+    // result would not fit in `columns`
+    pub fn annotate(&mut self, suffix: &str, columns: usize) {
+        let whole = self.uncut.take().unwrap_or_else(|| self.text.clone()) + suffix;
+        self.text = annotated(&self.text, suffix, columns);
+        self.uncut = (whole.chars().count() > columns).then_some(whole);
+    }
+}
+
+fn annotated(text: &str, suffix: &str, columns: usize) -> String {
+    let width = columns.saturating_sub(suffix.chars().count());
+    text.chars().take(width).collect::<String>() + suffix
 }
 
 impl PartialEq for Line {
@@ -48,37 +99,392 @@ impl Ord for Line {
     }
 }
 
+// what is written out: the kept lines, or for editors the ranges of input
+// lines to fold away (`12-87 collapsed`, numbered from 1)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Folds,
+}
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "folds" => Ok(OutputFormat::Folds),
+            _ => bail!("unknown format: {} (expected text or folds)", s),
+        }
+    }
+}
+
+// the text of a line without its colours
+fn plain_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        plain.push_str(&rest[..start]);
+        rest = &rest[start..];
+        rest = match rest.find('m') {
+            Some(end) => &rest[end + 1..],
+            None => "",
+        };
+    }
+    plain.push_str(rest);
+    plain
+}
+
+// `text` cut to `width` characters, the last one being `…` if it is cut
+fn shorten(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        text.chars()
+            .take(width.saturating_sub(1))
+            .collect::<String>()
+            + "…"
+    } else {
+        text.to_string()
+    }
+}
+
+// what a marker says about the lines it stands for, besides being there
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MarkerDetail {
+    #[default]
+    None,
+    // the start of the first omitted line, `e.g. "Compiling serde v1.0…"`
+    Preview,
+    // the most common shapes of the omitted lines, numbers aside,
+    // `: 118× `GET /healthz 200`, 2× other`
+    Summary,
+    // the numbers of the omitted input lines, ` [lines 140–212]`
+    Range,
+}
+impl FromStr for MarkerDetail {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "none" => Ok(MarkerDetail::None),
+            "preview" => Ok(MarkerDetail::Preview),
+            "summary" => Ok(MarkerDetail::Summary),
+            "range" => Ok(MarkerDetail::Range),
+            _ => bail!(
+                "unknown marker detail: {} (expected none, preview, summary or range)",
+                s
+            ),
+        }
+    }
+}
+
+// how an omitted block of lines is shown
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Marker {
+    #[default]
+    Plain,
+    // `... 42 lines ...`
+    Counted,
+    // `│   ├── ...`, keeping the indentation and tree branches of the first
+    // omitted line
+    Indented,
+}
+
 #[derive(Clone, Debug)]
 pub struct Lines {
     pub lines: Vec<Line>,
     pub target_lines: usize,
+    pub columns: usize,
+    pub marker: Marker,
+    pub marker_detail: MarkerDetail,
+    pub line_numbers: bool,
+    // at most this many markers, merging the closest omitted blocks
+    pub max_markers: Option<usize>,
+    // before any folding
+    pub input_lines: usize,
 }
 
 impl Lines {
+    #[cfg(test)]
     pub fn from_reader<R: BufRead>(
         reader: R,
         columns: usize,
         target_lines: usize,
+    ) -> Result<Lines> {
+        Lines::from_reader_with_directives(reader, columns, target_lines, None)
+    }
+
+    // directives in comments starting with `comment_prefix` are read along
+    // with the escape sequence ones
+    pub fn from_reader_with_directives<R: BufRead>(
+        reader: R,
+        columns: usize,
+        target_lines: usize,
+        comment_prefix: Option<&str>,
     ) -> Result<Lines> {
         let lines: Vec<String> = reader.lines().collect::<Result<Vec<String>, _>>()?;
-        let lines: Vec<Line> = lines.iter().map(|l| Line::new(&l, columns)).collect();
+        let lines: Vec<Line> = lines
+            .iter()
+            .enumerate()
+            .map(|(source, l)| {
+                let (pin, l) = match preprocess::take_directive(l, comment_prefix) {
+                    Some((pin, text)) => (Some(pin), text),
+                    None => (None, l.clone()),
+                };
+                Line {
+                    source,
+                    pin,
+                    ..Line::new(&preprocess::sanitize(&expose_section_marker(&l)), columns)
+                }
+            })
+            .collect();
         Ok(Lines {
+            input_lines: lines.len(),
             lines,
             target_lines,
+            columns,
+            marker: Marker::default(),
+            marker_detail: MarkerDetail::default(),
+            line_numbers: false,
+            max_markers: None,
         })
     }
 
     pub fn write<W: Write>(&self, mut writer: W) -> Result<()> {
+        for (_, row) in self.rows() {
+            writeln!(writer, "{}", row)?;
+        }
+        Ok(())
+    }
+
+    // what `write` prints, line by line, with the number of the line each
+    // row stands for
+    pub fn rows(&self) -> Vec<(usize, String)> {
+        let mut n_similar = vec![0; self.lines.len()];
+        for line in &self.lines {
+            if let Some(similar_to) = line.similar_to
+                && (line.status == LineStatus::Discarded || line.status == LineStatus::DotDotDot)
+            {
+                n_similar[similar_to] += 1;
+            }
+        }
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(line_number, line)| match line.status {
+                LineStatus::Kept | LineStatus::Discardable if n_similar[line_number] > 0 => {
+                    let suffix = format!(" (+{} similar)", n_similar[line_number]);
+                    let text = annotated(
+                        &line.text,
+                        &suffix,
+                        self.columns.saturating_sub(self.gutter()),
+                    );
+                    Some((line_number, self.numbered(Some(line.source), &text)))
+                }
+                LineStatus::Kept | LineStatus::Discardable => {
+                    Some((line_number, self.numbered(Some(line.source), &line.text)))
+                }
+                LineStatus::DotDotDot => Some((
+                    line_number,
+                    self.numbered(None, &self.marker_text(line_number)),
+                )),
+                LineStatus::Discarded => None,
+            })
+            .collect()
+    }
+
+    // width of the column of line numbers, with the space after it
+    fn gutter(&self) -> usize {
+        if self.line_numbers {
+            self.input_lines.max(1).to_string().len() + 1
+        } else {
+            0
+        }
+    }
+
+    // `text` after the number of the input line it comes from (blank for
+    // markers), cut to fit, when line numbers are shown
+    fn numbered(&self, source: Option<usize>, text: &str) -> String {
+        if !self.line_numbers {
+            return text.to_string();
+        }
+        let gutter = self.gutter();
+        let number = source.map_or(String::new(), |s| (s + 1).to_string());
+        let text: String = text
+            .chars()
+            .take(self.columns.saturating_sub(gutter))
+            .collect();
+        format!("{:>width$} {}", number, text, width = gutter - 1)
+    }
+
+    // every line, the ones pruning gave up dimmed rather than left out.
+    // resets in the text keep the dimming on
+    pub fn write_dimmed<W: Write>(&self, mut writer: W) -> Result<()> {
         for line in &self.lines {
             match line.status {
-                LineStatus::Kept | LineStatus::Discardable => writeln!(writer, "{}", line.text)?,
-                LineStatus::DotDotDot => writeln!(writer, "{}", DOTDOTDOT)?,
-                LineStatus::Discarded => (),
+                LineStatus::Kept | LineStatus::Discardable => {
+                    writeln!(writer, "{}", self.numbered(Some(line.source), &line.text))?
+                }
+                LineStatus::DotDotDot | LineStatus::Discarded => {
+                    let text = line.text.replace("\x1b[0m", DIM).replace("\x1b[m", DIM);
+                    let text = self.numbered(Some(line.source), &text);
+                    writeln!(writer, "{}{}\x1b[0m", DIM, text)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // for tools following the output: the row of each printed line, and the
+    // input line it comes from, or the range of input lines a marker stands
+    // for, e.g. `3 5-41`. numbered from 1
+    pub fn write_map<W: Write>(&self, mut writer: W) -> Result<()> {
+        for (row, (line_number, _)) in self.rows().iter().enumerate() {
+            if self.lines[*line_number].status == LineStatus::DotDotDot {
+                let range = self.source_range(self.omitted_block(*line_number));
+                writeln!(writer, "{} {}-{}", row + 1, range.start + 1, range.end)?;
+            } else {
+                writeln!(
+                    writer,
+                    "{} {}",
+                    row + 1,
+                    self.lines[*line_number].source + 1
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // every run of input lines that is not printed, markers included
+    pub fn write_folds<W: Write>(&self, mut writer: W) -> Result<()> {
+        let mut next = 0;
+        let shown = self
+            .lines
+            .iter()
+            .filter(|l| l.status == LineStatus::Kept || l.status == LineStatus::Discardable)
+            .map(|l| l.source);
+        for source in shown.chain([self.input_lines]) {
+            if source > next {
+                writeln!(writer, "{}-{} collapsed", next + 1, source)?;
             }
+            next = source + 1;
         }
         Ok(())
     }
 
+    // the marker replaces its own line and the discarded ones following it
+    fn marker_text(&self, line_number: usize) -> String {
+        let marker = |detail: &str| match self.marker {
+            Marker::Plain if detail.is_empty() => String::from(DOTDOTDOT),
+            Marker::Plain => format!("{}{} {}", DOTDOTDOT, detail, DOTDOTDOT),
+            Marker::Counted => {
+                let n_omitted = self.omitted_block(line_number).len();
+                format!("{} {} lines{} {}", DOTDOTDOT, n_omitted, detail, DOTDOTDOT)
+            }
+            Marker::Indented => {
+                let indentation: String = self.lines[line_number]
+                    .text
+                    .chars()
+                    .take_while(|c| c.is_whitespace() || "│├└─|`-".contains(*c))
+                    .collect();
+                format!("{}{}{}", indentation, DOTDOTDOT, detail)
+            }
+        };
+        let room = self
+            .columns
+            .saturating_sub(self.gutter() + marker("").chars().count());
+        marker(&self.describe_block(line_number, room).unwrap_or_default())
+    }
+
+    // what the marker tells of the lines it stands for, starting with its
+    // separator, in at most `room` columns
+    fn describe_block(&self, line_number: usize, room: usize) -> Option<String> {
+        match self.marker_detail {
+            MarkerDetail::None => None,
+            MarkerDetail::Preview => {
+                let sample = plain_text(&self.lines[line_number].text);
+                // ` (e.g. "")` and a few characters
+                let width = room.checked_sub(10).filter(|w| *w >= MIN_SAMPLE_WIDTH)?;
+                Some(format!(" (e.g. \"{}\")", shorten(sample.trim(), width)))
+            }
+            MarkerDetail::Range => {
+                let range = self.source_range(self.omitted_block(line_number));
+                let detail = if range.len() == 1 {
+                    format!(" [line {}]", range.end)
+                } else {
+                    format!(" [lines {}–{}]", range.start + 1, range.end)
+                };
+                (detail.chars().count() <= room).then_some(detail)
+            }
+            MarkerDetail::Summary => {
+                let block = self.omitted_block(line_number);
+                let total = block.len();
+                // shape, first line of that shape, count
+                let mut groups: Vec<(String, String, usize)> = Vec::new();
+                for line in &self.lines[block] {
+                    let text = plain_text(&line.text).trim().to_string();
+                    let shape = preprocess::counter_shape(&text);
+                    match groups.iter_mut().find(|g| g.0 == shape) {
+                        Some(group) => group.2 += 1,
+                        None => groups.push((shape, text, 1)),
+                    }
+                }
+                groups.sort_by_key(|g| Reverse(g.2));
+                let mut summary = String::new();
+                let mut counted = 0;
+                for (_, text, count) in groups.iter().take(MAX_SUMMARY_GROUPS) {
+                    let separator = if summary.is_empty() { ":" } else { "," };
+                    let head = format!("{} {}× `", separator, count);
+                    let rest = total - counted - count;
+                    let other = if rest > 0 {
+                        format!(", {}× other", rest)
+                    } else {
+                        String::new()
+                    };
+                    let used = [&summary, &head, "`", &other]
+                        .iter()
+                        .map(|s| s.chars().count())
+                        .sum();
+                    let Some(width) = room.checked_sub(used).filter(|w| *w >= MIN_SAMPLE_WIDTH)
+                    else {
+                        break;
+                    };
+                    summary += &format!("{}{}`", head, shorten(text, width));
+                    counted += count;
+                }
+                if counted == 0 {
+                    return None;
+                }
+                if counted < total {
+                    summary += &format!(", {}× other", total - counted);
+                }
+                Some(summary)
+            }
+        }
+    }
+
+    // the input lines that `lines` were read from, folded ones included
+    pub fn source_range(&self, lines: Range<usize>) -> Range<usize> {
+        let end = self
+            .lines
+            .get(lines.end)
+            .map_or(self.input_lines, |l| l.source);
+        self.lines[lines.start].source..end
+    }
+
+    // the lines a marker stands for: its own and the discarded ones after it
+    pub fn omitted_block(&self, line_number: usize) -> Range<usize> {
+        let n_discarded = self.lines[line_number + 1..]
+            .iter()
+            .take_while(|l| l.status == LineStatus::Discarded)
+            .count();
+        line_number..line_number + 1 + n_discarded
+    }
+
+    // whether there are at most `ratio` times as many lines as fit
+    pub fn fit_within(&self, ratio: f64) -> bool {
+        self.lines.len() as f64 <= ratio * self.target_lines as f64
+    }
+
     pub fn kept_lines(&self) -> usize {
         self.lines
             .iter()
@@ -90,19 +496,94 @@ impl Lines {
             .count()
     }
 
+    // gives up lines until at most `target_lines` are shown, markers
+    // included. only statuses change, so shown lines keep their input order,
+    // and pruning again to the same target changes nothing
+    //
+    // with no room at all nothing is shown, with a single line a marker
     pub fn prune(&mut self) {
-        while self.kept_lines() > self.target_lines {
-            //dbg!(self.kept_lines());
-            // kept to discardable (one line)
-            if let Some(line_to_delete) = self
-                .lines
-                .iter_mut()
-                .filter(|l| l.status == LineStatus::Kept)
-                .max()
-            {
-                line_to_delete.status = LineStatus::Discardable;
-            } else {
-                panic!("no more lines prune!");
+        if self.target_lines == 0 {
+            for line in self.lines.iter_mut() {
+                line.status = LineStatus::Discarded;
+            }
+            return;
+        }
+        if self.max_markers.is_none() {
+            self.prune_to(self.target_lines);
+            return;
+        }
+        // merging markers frees rows, pruning anew to a larger budget fills
+        // them. always from the start, so that pruning again changes nothing
+        let mut budget = self.target_lines;
+        let mut fitting: Option<Vec<LineStatus>> = None;
+        loop {
+            for line in self.lines.iter_mut() {
+                line.status = LineStatus::Kept;
+            }
+            self.prune_to(budget);
+            self.merge_markers();
+            if self.kept_lines() > self.target_lines {
+                break;
+            }
+            fitting = Some(self.lines.iter().map(|l| l.status.clone()).collect());
+            if self.kept_lines() == self.target_lines || budget >= self.lines.len() {
+                break;
+            }
+            budget += 1;
+        }
+        if let Some(statuses) = fitting {
+            for (line, status) in self.lines.iter_mut().zip(statuses) {
+                line.status = status;
+            }
+        }
+    }
+
+    fn prune_to(&mut self, target_lines: usize) {
+        while self.kept_lines() > target_lines {
+            // parents go only once none of their children are left, so
+            // whole subtrees collapse from the leaves up. every ancestor of a
+            // kept line stays, even when a line between them gave way
+            let mut has_kept_child = vec![false; self.lines.len()];
+            for (line_number, line) in self.lines.iter().enumerate() {
+                if line.status != LineStatus::Kept {
+                    continue;
+                }
+                let mut child = line_number;
+                while let Some(parent) = self.lines[child].parent.filter(|p| *p < child) {
+                    if has_kept_child[parent] {
+                        break;
+                    }
+                    has_kept_child[parent] = true;
+                    child = parent;
+                }
+            }
+            // lines sharing a head go together, as important as the most
+            // important of them
+            let group = |(i, l): (usize, &Line)| l.head.filter(|h| *h < i).unwrap_or(i);
+            let mut best: Vec<Option<&Vec<u32>>> = vec![None; self.lines.len()];
+            let mut blocked = vec![false; self.lines.len()];
+            for (line_number, line) in self.lines.iter().enumerate() {
+                if line.status == LineStatus::Kept {
+                    let g = group((line_number, line));
+                    best[g] = Some(best[g].map_or(&line.prio, |b| b.min(&line.prio)));
+                    blocked[g] |= has_kept_child[line_number];
+                }
+            }
+            // kept to discardable (one line, or one group). parents and heads
+            // pointing across each other can block every group, they give way
+            // then. with nothing kept there is nothing more to give up
+            let least_important = |ignore_blocks: bool| {
+                (0..self.lines.len())
+                    .filter(|g| best[*g].is_some() && (ignore_blocks || !blocked[*g]))
+                    .max_by(|a, b| best[*a].cmp(&best[*b]))
+            };
+            let Some(victim) = least_important(false).or_else(|| least_important(true)) else {
+                break;
+            };
+            for (line_number, line) in self.lines.iter_mut().enumerate() {
+                if line.status == LineStatus::Kept && group((line_number, line)) == victim {
+                    line.status = LineStatus::Discardable;
+                }
             }
 
             // discardable to discarded (zero or more lines)
@@ -126,6 +607,45 @@ impl Lines {
             }
         }
     }
+
+    // with `max_markers`, the two omitted blocks closest to each other become
+    // one, along with the lines between them, until there are few enough.
+    // blocks with a line pinned to stay between them are not merged
+    fn merge_markers(&mut self) {
+        let Some(max_markers) = self.max_markers else {
+            return;
+        };
+        loop {
+            let markers: Vec<usize> = (0..self.lines.len())
+                .filter(|n| self.lines[*n].status == LineStatus::DotDotDot)
+                .collect();
+            if markers.len() <= max_markers.max(1) {
+                return;
+            }
+            let shown_between = |(a, b): &(usize, usize)| {
+                self.lines[a + 1..*b]
+                    .iter()
+                    .filter(|l| l.status != LineStatus::Discarded)
+                    .count()
+            };
+            let pinned_between = |(a, b): &(usize, usize)| {
+                self.lines[a + 1..=*b]
+                    .iter()
+                    .any(|l| l.pin == Some(Pin::Keep))
+            };
+            let Some((first, second)) = markers
+                .windows(2)
+                .map(|w| (w[0], w[1]))
+                .filter(|pair| !pinned_between(pair))
+                .min_by_key(shown_between)
+            else {
+                return;
+            };
+            for line in &mut self.lines[first + 1..=second] {
+                line.status = LineStatus::Discarded;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,26 +711,56 @@ mod tests {
             prio: vec![10, 20, 30],
             text: String::from("x"),
             status: LineStatus::Kept,
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: None,
+            source: 0,
         };
         let second_line = Line {
             prio: vec![10, 21, 30],
             text: String::from("x"),
             status: LineStatus::Kept,
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: None,
+            source: 0,
         };
         let third_line = Line {
             prio: vec![11, 21, 30],
             text: String::from("x"),
             status: LineStatus::Kept,
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: None,
+            source: 0,
         };
         let fourth_line = Line {
             prio: vec![12],
             text: String::from("x"),
             status: LineStatus::Kept,
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: None,
+            source: 0,
         };
         let fifth_line = Line {
             prio: vec![12],
             text: String::from("y"),
             status: LineStatus::Kept,
+            parent: None,
+            head: None,
+            pin: None,
+            similar_to: None,
+            uncut: None,
+            source: 0,
         };
         expect_that!(first_line, lt(&second_line));
         expect_that!(second_line, lt(&third_line));
@@ -244,6 +794,164 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    fn write_folds() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 10, 10)?;
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        lines.lines[4].status = LineStatus::Discardable;
+        lines.lines.remove(5);
+        lines.write_folds(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq("2-3 collapsed\n6-6 collapsed\n")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_dimmed() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new("a\nb\x1b[0mc\nd\n".into());
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 10, 10)?;
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        lines.write_dimmed(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq("a\n\x1b[0;2mb\x1b[0;2mc\x1b[0m\n\x1b[0;2md\x1b[0m\n")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_counted_marker() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 10, 10)?;
+        lines.marker = Marker::Counted;
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        lines.lines[3].status = LineStatus::Discarded;
+        lines.write(&mut w)?;
+        let s: String = String::from_utf8(w.into_inner())?;
+        let expected: &str = "first\n... 3 lines ...\nfifth\nsixt\n";
+        expect_that!(s, eq(expected));
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_marker_preview() -> Result<()> {
+        let r: Cursor<Vec<u8>> =
+            Cursor::new("first\n  \x1b[1mCompiling serde v1.0.219\x1b[0m\nthird\nlast\n".into());
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 40, 10)?;
+        lines.marker = Marker::Counted;
+        lines.marker_detail = MarkerDetail::Preview;
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        lines.write(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq("first\n... 2 lines (e.g. \"Compiling serd…\") ...\nlast\n")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_marker_summary() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(
+            "first\nGET /healthz 200\nGET /healthz 200\nPOST /login 401\nGET /healthz 201\nlast\n"
+                .into(),
+        );
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 60, 10)?;
+        lines.marker = Marker::Counted;
+        lines.marker_detail = MarkerDetail::Summary;
+        lines.lines[1].status = LineStatus::DotDotDot;
+        for line in &mut lines.lines[2..5] {
+            line.status = LineStatus::Discarded;
+        }
+        lines.write(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq("first\n... 4 lines: 3× `GET /healthz 200`, 1× `POST /login 401` ...\nlast\n")
+        );
+        lines.columns = 48;
+        expect_that!(
+            lines.marker_text(1),
+            eq("... 4 lines: 3× `GET /healthz 200`, 1× other ...")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_marker_range() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
+        let mut lines = Lines::from_reader(r, 40, 10)?;
+        lines.marker_detail = MarkerDetail::Range;
+        lines.lines.remove(3);
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        // the folded fourth line goes with the block
+        expect_that!(lines.marker_text(1), eq("... [lines 2–4] ..."));
+        lines.columns = 10;
+        expect_that!(lines.marker_text(1), eq("..."));
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_line_numbers() -> Result<()> {
+        let text: String = (1..=12).map(|n| format!("line {}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 8, 10)?;
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        lines.line_numbers = true;
+        lines.lines.remove(1);
+        lines.lines[2].status = LineStatus::DotDotDot;
+        for line in &mut lines.lines[3..10] {
+            line.status = LineStatus::Discarded;
+        }
+        lines.write(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq(" 1 line \n 3 line \n   ...\n12 line \n")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_source_map() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 10, 10)?;
+        lines.lines.remove(0);
+        lines.lines[1].status = LineStatus::DotDotDot;
+        lines.lines[2].status = LineStatus::Discarded;
+        lines.write_map(&mut w)?;
+        expect_that!(
+            String::from_utf8(w.into_inner())?,
+            eq("1 2\n2 3-4\n3 5\n4 6\n")
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn write_indented_marker() -> Result<()> {
+        let r = Cursor::new(".\n├── src\n│   ├── lines.rs\n│   └── main.rs\n└── README.md\n");
+        let mut w: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut lines = Lines::from_reader(r, 20, 10)?;
+        lines.marker = Marker::Indented;
+        lines.lines[2].status = LineStatus::DotDotDot;
+        lines.lines[3].status = LineStatus::Discarded;
+        lines.write(&mut w)?;
+        let s: String = String::from_utf8(w.into_inner())?;
+        let expected: &str = ".\n├── src\n│   ├── ...\n└── README.md\n";
+        expect_that!(s, eq(expected));
+        Ok(())
+    }
+
     #[gtest]
     fn lines_trim_columns() -> Result<()> {
         let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
@@ -270,6 +978,132 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    fn fit_within() -> Result<()> {
+        let r: Cursor<Vec<u8>> = Cursor::new(MULTILINE.into());
+        let lines = Lines::from_reader(r, 10, 4)?;
+        expect_that!(lines.fit_within(1.0), eq(false));
+        expect_that!(lines.fit_within(1.5), eq(true));
+        Ok(())
+    }
+
+    // xorshift, to vary inputs without a dependency
+    fn pseudo_random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[gtest]
+    fn prune_keeps_order_and_is_idempotent() -> Result<()> {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..200 {
+            let n = 1 + pseudo_random(&mut state) % 40;
+            let target = 1 + (pseudo_random(&mut state) % n) as usize;
+            let text: String = (0..n).map(|i| format!("{}\n", i)).collect();
+            let mut lines = Lines::from_reader(Cursor::new(text), 10, target)?;
+            for (line_number, line) in lines.lines.iter_mut().enumerate() {
+                line.prio.push((pseudo_random(&mut state) % 5) as u32);
+                if line_number > 0 && pseudo_random(&mut state).is_multiple_of(4) {
+                    line.head = Some((pseudo_random(&mut state) % line_number as u64) as usize);
+                }
+                if line_number > 0 && pseudo_random(&mut state).is_multiple_of(4) {
+                    line.parent = Some((pseudo_random(&mut state) % line_number as u64) as usize);
+                }
+            }
+            lines.prune();
+            let rows = lines.rows();
+            expect_that!(rows.len(), le(target));
+            expect_that!(rows.windows(2).all(|w| w[0].0 < w[1].0), eq(true));
+            // omitted lines always follow a marker, counted in the rows
+            let mut after_marker = false;
+            for line in &lines.lines {
+                if line.status == LineStatus::Discarded {
+                    expect_that!(after_marker, eq(true));
+                }
+                after_marker = matches!(line.status, LineStatus::DotDotDot | LineStatus::Discarded);
+            }
+            let statuses: Vec<LineStatus> = lines.lines.iter().map(|l| l.status.clone()).collect();
+            lines.prune();
+            expect_that!(
+                lines
+                    .lines
+                    .iter()
+                    .map(|l| l.status.clone())
+                    .collect::<Vec<_>>(),
+                eq(&statuses)
+            );
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_degenerate_sizes() -> Result<()> {
+        let mut lines = make_lines_head();
+        lines.target_lines = 0;
+        lines.prune();
+        expect_that!(lines.rows(), is_empty());
+        let mut lines = make_lines_tail();
+        lines.target_lines = 1;
+        lines.prune();
+        expect_that!(lines.rows(), eq(&vec![(0, "...".to_string())]));
+        let mut lines = Lines::from_reader(Cursor::new(""), 10, 0)?;
+        lines.prune();
+        expect_that!(lines.rows(), is_empty());
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_blocked_groups() -> Result<()> {
+        let mut lines = make_lines_head();
+        // the first and last lines, and the third and fifth, go together,
+        // each group holding the parent of the other
+        lines.lines[5].head = Some(0);
+        lines.lines[4].head = Some(2);
+        lines.lines[2].parent = Some(0);
+        lines.lines[5].parent = Some(2);
+        lines.target_lines = 1;
+        lines.prune();
+        expect_that!(lines.kept_lines(), eq(1));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_merging_markers() -> Result<()> {
+        let text: String = (0..12).map(|n| format!("{}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 10, 8)?;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(if line_number % 3 == 0 { 0 } else { 1 });
+        }
+        lines.prune();
+        expect_that!(lines.rows().iter().filter(|r| r.1 == "...").count(), eq(4));
+        lines.max_markers = Some(2);
+        lines.prune();
+        let rows: Vec<String> = lines.rows().into_iter().map(|r| r.1).collect();
+        // the rows freed by merging are filled again
+        expect_that!(rows, eq(&vec!["0", "1", "2", "3", "...", "9", "..."]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_merging_around_pins() -> Result<()> {
+        let text: String = (0..40).map(|n| format!("{}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 10, 10)?;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let pinned = line_number == 30;
+            line.prio.push(if pinned { 0 } else { 1 });
+            line.prio.push(line_number.min(39 - line_number) as u32);
+            line.pin = pinned.then_some(Pin::Keep);
+        }
+        lines.max_markers = Some(1);
+        lines.prune();
+        let rows: Vec<String> = lines.rows().into_iter().map(|r| r.1).collect();
+        expect_that!(rows.len(), eq(10));
+        expect_that!(rows.contains(&"30".to_string()), eq(true));
+        Ok(())
+    }
+
     #[gtest]
     fn prune_head() -> Result<()> {
         let mut lines = make_lines_head();
@@ -392,4 +1226,63 @@ mod tests {
         expect_that!(lines.kept_lines(), ge(3));
         Ok(())
     }
+
+    #[gtest]
+    fn prune_keeps_parents() -> Result<()> {
+        let mut lines = make_lines_tail();
+        // `fifth` and `sixt` hang below `first`, the least important line
+        lines.lines[4].parent = Some(0);
+        lines.lines[5].parent = Some(4);
+        lines.target_lines = 4;
+        lines.prune();
+        expect_that!(lines.lines[0].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[1].status, eq(&LineStatus::DotDotDot));
+        expect_that!(lines.lines[4].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[5].status, eq(&LineStatus::Kept));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_keeps_ancestors() -> Result<()> {
+        let text = "root\nchild\ngrandchild\nleaf\n";
+        let mut lines = Lines::from_reader(Cursor::new(text), 100, 2)?;
+        for (line, prio) in lines.lines.iter_mut().zip([3, 4, 1, 5]) {
+            line.prio.push(prio);
+        }
+        lines.lines[1].parent = Some(0);
+        lines.lines[2].parent = Some(1);
+        lines.lines[3].parent = Some(2);
+        // with every group blocked, `child` and `leaf` give way first. the
+        // grandchild still can't be shown without the root
+        lines.lines[3].head = Some(1);
+        lines.prune();
+        let rows: Vec<String> = lines.rows().into_iter().map(|r| r.1).collect();
+        expect_that!(rows, eq(&vec!["root", "..."]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prune_groups() -> Result<()> {
+        let mut lines = make_lines_tail();
+        // `fifth` goes with `fourth`, as important as itself
+        lines.lines[4].head = Some(3);
+        // `first` and `second` are the least important, they go in one step
+        lines.lines[1].head = Some(0);
+        lines.target_lines = 5;
+        lines.prune();
+        expect_that!(lines.lines[0].status, eq(&LineStatus::DotDotDot));
+        expect_that!(lines.lines[1].status, eq(&LineStatus::Discarded));
+        expect_that!(lines.lines[2].status, eq(&LineStatus::Kept));
+        lines.target_lines = 4;
+        lines.prune();
+        expect_that!(lines.lines[2].status, eq(&LineStatus::Discarded));
+        expect_that!(lines.lines[3].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[4].status, eq(&LineStatus::Kept));
+        lines.target_lines = 3;
+        lines.prune();
+        expect_that!(lines.lines[3].status, eq(&LineStatus::Discarded));
+        expect_that!(lines.lines[4].status, eq(&LineStatus::Discarded));
+        expect_that!(lines.kept_lines(), eq(2));
+        Ok(())
+    }
 }
@@ -1,27 +1,89 @@
-use std::io;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
 
-use anyhow::{Error, Result, bail};
+use anyhow::{Context, Error, Result, bail};
 use terminal_size::{Height, Width, terminal_size};
 
 mod cat;
+mod config;
+mod interactive;
 mod lines;
+mod options;
+mod preprocess;
 mod prioritizer;
+use crate::lines::OutputFormat;
+use crate::options::Options;
 use crate::prioritizer::Prioritizer;
 
-const EXTRA_LINES_TO_DELETE: usize = 2; // allows to read last executed command and next one
+const DEFAULT_RESERVE: usize = 2; // allows to read last executed command and next one
 
 fn main() -> Result<()> {
+    let options = Options::parse(std::env::args().skip(1))?;
+    if options.help {
+        print!("{}", options::USAGE);
+        return Ok(());
+    }
+    let config = config::Config::load(options.config.as_deref())?;
+    // shell hooks can export the height of multi-line prompts
+    let reserve = options
+        .reserve
+        .or_else(|| std::env::var("ONELESS_RESERVE").ok()?.parse().ok())
+        .or(config.reserve)
+        .unwrap_or(DEFAULT_RESERVE);
     let stdin = io::stdin().lock();
     let stdout = io::stdout();
 
-    match terminal_size() {
-        None => bail!("stdout not a TTY (unable to determine size)"),
+    // editors running oneless through a pipe (vim does) export the size of
+    // their window instead
+    let size = terminal_size().or_else(|| {
+        let from_env = |name| std::env::var(name).ok()?.parse().ok();
+        Some((Width(from_env("COLUMNS")?), Height(from_env("LINES")?)))
+    });
+    match size {
+        None => bail!("stdout not a TTY (unable to determine size, set LINES and COLUMNS)"),
         Some((Width(w), Height(h))) => {
-            let mut l =
-                lines::Lines::from_reader(stdin, w as usize, h as usize - EXTRA_LINES_TO_DELETE)?;
-            prioritizer::auto_prioritize(&mut l)?;
-            l.prune();
-            l.write(stdout)?;
+            let mut target_lines = (h as usize).saturating_sub(reserve);
+            if let Some(ratio) = options.max_height {
+                target_lines = target_lines.min((h as f64 * ratio) as usize);
+            }
+            let mut l = lines::Lines::from_reader_with_directives(
+                stdin,
+                w as usize,
+                target_lines,
+                options.directive_prefix.as_deref(),
+            )?;
+            l.marker_detail = options.marker_detail;
+            l.line_numbers = options.line_numbers;
+            l.max_markers = options.max_markers;
+            preprocess::pin_summaries(&mut l)?;
+            preprocess::fold_progress(&mut l);
+            preprocess::fold_duplicates(&mut l);
+            preprocess::mark_continuations(&mut l);
+            if let Some(separator) = &options.record_separator {
+                preprocess::mark_records(&mut l, separator)?;
+            }
+            prioritizer::auto_prioritize(&mut l, &options, &config)?;
+            preprocess::shorten_cut_lines(&mut l);
+            if options.interactive {
+                return interactive::run(&l, options.alt_screen.unwrap_or(true));
+            }
+            // slightly too long output is better scrolled than summarized
+            if !options.threshold.is_some_and(|ratio| l.fit_within(ratio)) {
+                l.prune();
+            }
+            let barely_over = options.dim_threshold.is_some_and(|ratio| l.fit_within(ratio));
+            if let Some(path) = &options.map {
+                let file =
+                    File::create(path).with_context(|| format!("unable to write {}", path))?;
+                let mut writer = BufWriter::new(file);
+                l.write_map(&mut writer)?;
+                writer.flush()?;
+            }
+            match options.format {
+                OutputFormat::Text if barely_over => l.write_dimmed(stdout)?,
+                OutputFormat::Text => l.write(stdout)?,
+                OutputFormat::Folds => l.write_folds(stdout)?,
+            }
         }
     }
     Ok(())
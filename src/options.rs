@@ -0,0 +1,372 @@
+use crate::lines::{MarkerDetail, OutputFormat};
+use crate::preprocess::RecordSeparator;
+use crate::prioritizer::{LengthPreference, LsCriterion};
+use anyhow::{Context, Result, bail};
+
+pub const USAGE: &str = "usage: oneless [OPTIONS] < input
+
+like cat, but only works on stdin, and prints just enough lines to fill the terminal
+
+options:
+  --outlier-column N   for csv input, also keep rows with an outlier value in column N (from 1)
+  --ls-sort CRITERION  for ls -l input, keep the biggest (size), newest (mtime) or
+                       directories (dirs) first, defaults to size
+  --line-length PREF   break ties by keeping shorter or longer lines first
+  --ref-pattern REGEX  also treat lines matching REGEX as references worth keeping,
+                       on top of file:line locations and urls (repeatable)
+  --directive-prefix P keep lines ending in `P keep` and give up `P drop` ones first,
+                       as the `\\e]1337;oneless=keep\\a` (or `=drop`) escape does
+  --score EXPR         keep the lines where EXPR is lowest first, e.g.
+                       'indent*10 + (contains(\"ERROR\") ? -100 : 0)'. EXPR can use
+                       indent, length, line, words, contains(\"s\"), starts_with(\"s\"),
+                       ends_with(\"s\"), matches(\"regex\"), arithmetic, comparisons,
+                       && || ! and c ? a : b
+  --prio-regex REGEX   keep the lines where the number captured by REGEX is lowest first,
+                       e.g. 'took (\\d+)ms'
+  --prio-field N       keep the lines with the lowest value in field N (from 1) first,
+                       fields are separated by whitespace unless --delimiter is given
+  --delimiter SEP      with --prio-field, split fields on SEP
+  --numeric            with --prio-field, compare values as numbers rather than text
+  --reverse            with --score, --prio-regex or --prio-field, keep the highest
+                       values first
+  --head-ratio PERCENT when keeping the start and end of the input, take PERCENT of
+                       the lines from the start, 50% by default, 0% for the end only
+  -p NAME:OPTION=VALUE set an option of a prioritizer, e.g. path-depth:sep=:: or
+                       head-and-tail:head=70% (repeatable)
+  --invert-priority    keep the lines that would be dropped first, to see what oneless
+                       finds unimportant
+  --record-separator S keep or drop whole records rather than lines, a record starts
+                       after blank lines (blank) or at lines matching a regex
+                       (regex:PATTERN)
+  -n, --line-numbers   print the number each line has in the input before it
+  --marker-detail D    say more in the markers of omitted lines: nothing (none), the
+                       start of the first omitted line (preview), how many of each
+                       kind of line were omitted (summary), or their line numbers
+                       in the input (range)
+  --max-markers N      show at most N markers of omitted lines, merging the closest
+                       omitted blocks and the lines between them
+  --map FILE           write to FILE the input line number of each printed line, or the
+                       range of input lines a marker stands for, as `ROW LINE` or
+                       `ROW FIRST-LAST`
+  --format FORMAT      print the kept lines (text), or the ranges of input lines to
+                       collapse, e.g. `12-87 collapsed`, for editors to fold (folds)
+  --reserve N          leave N terminal lines free for the prompt, defaults to
+                       $ONELESS_RESERVE, then to the config, then to 2
+  --max-height PERCENT use at most PERCENT (e.g. 50%) of the terminal height
+  --threshold RATIO    print every line unless there are more than RATIO (e.g. 1.5)
+                       times the lines that fit
+  --prune-only-if-over PERCENT
+                       print every line when there are at most PERCENT (e.g. 150%)
+                       of the lines that fit, dimming those that would be left out
+  --config FILE        read the config from FILE rather than
+                       ~/.config/oneless/config.toml
+  -i, --interactive    show the condensed view, then more of the hidden lines, most
+                       important first, each time space is pressed
+  --no-alt-screen      with --interactive, draw below the prompt rather than on the
+                       alternate screen, leaving the last view in the scrollback
+  --alt-screen         with --interactive, draw on the alternate screen, restoring the
+                       terminal on exit (the default, overrides an earlier
+                       --no-alt-screen)
+  -h, --help           print this help
+";
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    pub help: bool,
+    pub outlier_column: Option<usize>,
+    pub ls_criterion: LsCriterion,
+    pub line_length: Option<LengthPreference>,
+    pub reference_patterns: Vec<String>,
+    pub directive_prefix: Option<String>,
+    pub score: Option<String>,
+    pub prio_regex: Option<String>,
+    pub prio_field: Option<usize>,
+    pub delimiter: Option<String>,
+    pub numeric: bool,
+    pub reverse: bool,
+    pub config: Option<String>,
+    pub record_separator: Option<RecordSeparator>,
+    pub head_ratio: Option<f64>,
+    pub invert: bool,
+    // prioritizer, option, value
+    pub prioritizer_options: Vec<(String, String, String)>,
+    pub format: OutputFormat,
+    pub map: Option<String>,
+    pub max_markers: Option<usize>,
+    pub marker_detail: MarkerDetail,
+    pub line_numbers: bool,
+    pub interactive: bool,
+    pub reserve: Option<usize>,
+    // as a ratio of the terminal height
+    pub max_height: Option<f64>,
+    pub threshold: Option<f64>,
+    // as a ratio, 1.5 for 150%
+    pub dim_threshold: Option<f64>,
+    // unset means the mode's default
+    pub alt_screen: Option<bool>,
+}
+
+// value of `--flag value` or `--flag=value`
+fn value<I: Iterator<Item = String>>(
+    flag: &str,
+    inline_value: Option<&str>,
+    args: &mut I,
+) -> Result<String> {
+    match inline_value {
+        Some(v) => Ok(v.to_string()),
+        None => args
+            .next()
+            .with_context(|| format!("missing value for {}", flag)),
+    }
+}
+
+// `150%` (or `150`) as 1.5
+pub fn percentage(flag: &str, value: &str) -> Result<f64> {
+    let percent: f64 = value
+        .strip_suffix('%')
+        .unwrap_or(value)
+        .parse()
+        .with_context(|| format!("invalid value for {}", flag))?;
+    if percent.is_nan() || percent < 0.0 {
+        bail!("invalid value for {}: {}", flag, value);
+    }
+    Ok(percent / 100.0)
+}
+
+// the share of kept lines taken from the start, so no more than all of them
+pub fn head_ratio(flag: &str, value: &str) -> Result<f64> {
+    let ratio = percentage(flag, value)?;
+    if ratio > 1.0 {
+        bail!("{} is at most 100%", flag);
+    }
+    Ok(ratio)
+}
+
+impl Options {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((f, v)) if f.starts_with("--") => (f, Some(v)),
+                _ => (arg.as_str(), None),
+            };
+            match flag {
+                "-h" | "--help" => options.help = true,
+                "--outlier-column" => {
+                    let column: usize = value(flag, inline_value, &mut args)?
+                        .parse()
+                        .with_context(|| format!("invalid value for {}", flag))?;
+                    if column == 0 {
+                        bail!("columns are numbered from 1");
+                    }
+                    options.outlier_column = Some(column);
+                }
+                "--ls-sort" => {
+                    options.ls_criterion = value(flag, inline_value, &mut args)?.parse()?;
+                }
+                "--line-length" => {
+                    options.line_length = Some(value(flag, inline_value, &mut args)?.parse()?);
+                }
+                "--ref-pattern" => {
+                    let pattern = value(flag, inline_value, &mut args)?;
+                    options.reference_patterns.push(pattern);
+                }
+                "--directive-prefix" => {
+                    options.directive_prefix = Some(value(flag, inline_value, &mut args)?);
+                }
+                "--score" => options.score = Some(value(flag, inline_value, &mut args)?),
+                "--prio-regex" => options.prio_regex = Some(value(flag, inline_value, &mut args)?),
+                "--prio-field" => {
+                    let field: usize = value(flag, inline_value, &mut args)?
+                        .parse()
+                        .with_context(|| format!("invalid value for {}", flag))?;
+                    if field == 0 {
+                        bail!("fields are numbered from 1");
+                    }
+                    options.prio_field = Some(field);
+                }
+                "--delimiter" => options.delimiter = Some(value(flag, inline_value, &mut args)?),
+                "--numeric" => options.numeric = true,
+                "--reverse" => options.reverse = true,
+                "--config" => options.config = Some(value(flag, inline_value, &mut args)?),
+                "--record-separator" => {
+                    options.record_separator = Some(value(flag, inline_value, &mut args)?.parse()?);
+                }
+                "--head-ratio" => {
+                    options.head_ratio =
+                        Some(head_ratio(flag, &value(flag, inline_value, &mut args)?)?);
+                }
+                "-p" | "--prioritizer-option" => {
+                    let setting = value(flag, inline_value, &mut args)?;
+                    let Some((prioritizer, (option, value))) = setting
+                        .split_once(':')
+                        .and_then(|(p, rest)| Some((p, rest.split_once('=')?)))
+                    else {
+                        bail!("expected NAME:OPTION=VALUE for {}, found {}", flag, setting);
+                    };
+                    options.prioritizer_options.push((
+                        prioritizer.replace('-', "_"),
+                        option.to_string(),
+                        value.to_string(),
+                    ));
+                }
+                "--invert-priority" => options.invert = true,
+                "--reserve" => {
+                    let reserve = value(flag, inline_value, &mut args)?
+                        .parse()
+                        .with_context(|| format!("invalid value for {}", flag))?;
+                    options.reserve = Some(reserve);
+                }
+                "--max-height" => {
+                    let ratio = percentage(flag, &value(flag, inline_value, &mut args)?)?;
+                    if ratio > 1.0 {
+                        bail!("{} is at most 100%", flag);
+                    }
+                    options.max_height = Some(ratio);
+                }
+                "--threshold" => {
+                    let ratio: f64 = value(flag, inline_value, &mut args)?
+                        .parse()
+                        .with_context(|| format!("invalid value for {}", flag))?;
+                    if ratio.is_nan() || ratio < 0.0 {
+                        bail!("invalid value for {}: {}", flag, ratio);
+                    }
+                    options.threshold = Some(ratio);
+                }
+                "--prune-only-if-over" => {
+                    options.dim_threshold =
+                        Some(percentage(flag, &value(flag, inline_value, &mut args)?)?);
+                }
+                "-i" | "--interactive" => options.interactive = true,
+                "--no-alt-screen" => options.alt_screen = Some(false),
+                "--alt-screen" => options.alt_screen = Some(true),
+                "-n" | "--line-numbers" => options.line_numbers = true,
+                "--marker-detail" => {
+                    options.marker_detail = value(flag, inline_value, &mut args)?.parse()?;
+                }
+                "--max-markers" => {
+                    let max_markers: usize = value(flag, inline_value, &mut args)?
+                        .parse()
+                        .with_context(|| format!("invalid value for {}", flag))?;
+                    if max_markers == 0 {
+                        bail!("{} needs at least one marker", flag);
+                    }
+                    options.max_markers = Some(max_markers);
+                }
+                "--map" => options.map = Some(value(flag, inline_value, &mut args)?),
+                "--format" => options.format = value(flag, inline_value, &mut args)?.parse()?,
+                _ => bail!("unknown argument: {}\n\n{}", arg, USAGE),
+            }
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+
+    fn parse(args: &[&str]) -> Result<Options> {
+        Options::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[gtest]
+    fn parse_options() -> Result<()> {
+        expect_that!(parse(&[])?, eq(&Options::default()));
+        expect_that!(parse(&["--help"])?.help, eq(true));
+        expect_that!(
+            parse(&["--outlier-column", "3"])?.outlier_column,
+            some(eq(3))
+        );
+        expect_that!(parse(&["--outlier-column=2"])?.outlier_column, some(eq(2)));
+        expect_that!(
+            parse(&["--ls-sort", "mtime"])?.ls_criterion,
+            eq(LsCriterion::Mtime)
+        );
+        expect_that!(
+            parse(&["--line-length=longer"])?.line_length,
+            some(eq(LengthPreference::Longer))
+        );
+        expect_that!(
+            parse(&["--ref-pattern", "a", "--ref-pattern=b"])?.reference_patterns,
+            eq(&vec!["a".to_string(), "b".to_string()])
+        );
+        expect_that!(
+            parse(&["--directive-prefix", "# oneless:"])?.directive_prefix,
+            some(eq("# oneless:"))
+        );
+        expect_that!(parse(&["--score", "indent"])?.score, some(eq("indent")));
+        let options = parse(&["--prio-regex=took (\\d+)ms", "--reverse"])?;
+        expect_that!(options.prio_regex, some(eq("took (\\d+)ms")));
+        expect_that!(options.reverse, eq(true));
+        let options = parse(&["--prio-field", "3", "--numeric", "--delimiter=,"])?;
+        expect_that!(options.prio_field, some(eq(3)));
+        expect_that!(options.numeric, eq(true));
+        expect_that!(options.delimiter, some(eq(",")));
+        expect_that!(parse(&["--config", "a.toml"])?.config, some(eq("a.toml")));
+        expect_that!(
+            parse(&["--record-separator=regex:^commit "])?.record_separator,
+            some(eq(&RecordSeparator::Regex("^commit ".to_string())))
+        );
+        expect_that!(
+            parse(&["--format", "folds"])?.format,
+            eq(OutputFormat::Folds)
+        );
+        expect_that!(parse(&["--head-ratio", "70%"])?.head_ratio, some(eq(0.7)));
+        expect_that!(parse(&["--invert-priority"])?.invert, eq(true));
+        expect_that!(
+            parse(&["-p", "path-depth:sep=::"])?.prioritizer_options,
+            eq(&vec![(
+                "path_depth".to_string(),
+                "sep".to_string(),
+                "::".to_string()
+            )])
+        );
+        expect_that!(parse(&["--reserve", "4"])?.reserve, some(eq(4)));
+        expect_that!(parse(&["--max-height", "50%"])?.max_height, some(eq(0.5)));
+        expect_that!(parse(&["--threshold=1.5"])?.threshold, some(eq(1.5)));
+        expect_that!(
+            parse(&["--prune-only-if-over", "150%"])?.dim_threshold,
+            some(eq(1.5))
+        );
+        expect_that!(
+            parse(&["--marker-detail=preview"])?.marker_detail,
+            eq(MarkerDetail::Preview)
+        );
+        expect_that!(parse(&["-n"])?.line_numbers, eq(true));
+        expect_that!(parse(&["--map", "out.map"])?.map, some(eq("out.map")));
+        expect_that!(parse(&["--max-markers=3"])?.max_markers, some(eq(3)));
+        expect_that!(parse(&["-i"])?.interactive, eq(true));
+        expect_that!(parse(&["--no-alt-screen"])?.alt_screen, some(eq(false)));
+        expect_that!(
+            parse(&["--no-alt-screen", "--alt-screen"])?.alt_screen,
+            some(eq(true))
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn parse_invalid_options() {
+        expect_that!(parse(&["--ls-sort", "name"]), err(anything()));
+        expect_that!(parse(&["--outlier-column"]), err(anything()));
+        expect_that!(parse(&["--outlier-column", "x"]), err(anything()));
+        expect_that!(parse(&["--outlier-column", "0"]), err(anything()));
+        expect_that!(parse(&["--line-length", "medium"]), err(anything()));
+        expect_that!(parse(&["--prio-field", "0"]), err(anything()));
+        expect_that!(parse(&["--record-separator", "x"]), err(anything()));
+        expect_that!(parse(&["--format", "json"]), err(anything()));
+        expect_that!(parse(&["--marker-detail", "all"]), err(anything()));
+        expect_that!(parse(&["--head-ratio", "120%"]), err(anything()));
+        expect_that!(parse(&["--reserve", "-1"]), err(anything()));
+        expect_that!(parse(&["--max-height", "150%"]), err(anything()));
+        expect_that!(parse(&["-p", "path-depth"]), err(anything()));
+        expect_that!(parse(&["--max-markers", "0"]), err(anything()));
+        expect_that!(parse(&["--threshold", "x"]), err(anything()));
+        expect_that!(parse(&["--prune-only-if-over", "lots"]), err(anything()));
+        expect_that!(parse(&["--prune-only-if-over", "-5%"]), err(anything()));
+        expect_that!(parse(&["--frobnicate"]), err(anything()));
+    }
+}
@@ -0,0 +1,645 @@
+use crate::lines::{Line, Lines, Pin};
+use crate::prioritizer::starts_event;
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+use std::str::FromStr;
+
+// passes over the input that run before any prioritizer sees it
+
+// what a terminal would have left of a line: `50%\r100%` becomes `100%`,
+// backspaced spinner frames disappear, and cursor movement escapes are
+// dropped while colours are kept. runs on the raw text, before it is cut to
+// the terminal width
+pub fn sanitize(text: &str) -> String {
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' if chars.peek() == Some(&'[') => {
+                let mut sequence = String::from("\u{1b}[");
+                chars.next();
+                for c in chars.by_ref() {
+                    sequence.push(c);
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+                if sequence.ends_with('m') {
+                    clean.push_str(&sequence);
+                }
+            }
+            // `ESC 7`/`ESC 8` save and restore the cursor
+            '\u{1b}' if matches!(chars.peek(), Some('7' | '8')) => {
+                chars.next();
+            }
+            '\u{8}' => {
+                clean.pop();
+            }
+            _ => clean.push(c),
+        }
+    }
+    let mut segments = clean.split('\r');
+    let first = segments.next().unwrap_or_default();
+    let last = segments.rfind(|s| !s.is_empty());
+    last.unwrap_or(first).to_string()
+}
+
+// directories kept at either end of a shortened path
+const PATH_HEAD: usize = 2;
+const PATH_TAIL: usize = 2;
+
+// `/home/u/projects/oneless/src/lines.rs` as `/home/u/…/src/lines.rs`, or
+// None when there is nothing to cut
+fn shorten_path(word: &str) -> Option<String> {
+    if word.contains("://") {
+        return None;
+    }
+    let parts: Vec<&str> = word.split('/').collect();
+    let head = PATH_HEAD + usize::from(word.starts_with('/'));
+    if parts.len() <= head + PATH_TAIL + 1 {
+        return None;
+    }
+    Some(format!(
+        "{}/…/{}",
+        parts[..head].join("/"),
+        parts[parts.len() - PATH_TAIL..].join("/")
+    ))
+}
+
+// a line too wide for the terminal with `~` for `$HOME` and without the
+// middle of its paths, longest first, until it fits if it can
+pub fn shorten_paths(text: &str, columns: usize, home: Option<&str>) -> String {
+    let width = |s: &str| s.chars().count();
+    if width(text) <= columns {
+        return text.to_string();
+    }
+    let mut words: Vec<String> = text
+        .split(' ')
+        .map(|w| match home {
+            Some(home)
+                if !home.is_empty() && w.starts_with(home) && w[home.len()..].starts_with('/') =>
+            {
+                format!("~{}", &w[home.len()..])
+            }
+            _ => w.to_string(),
+        })
+        .collect();
+    let mut longest_first: Vec<usize> = (0..words.len()).collect();
+    longest_first.sort_by_key(|i| std::cmp::Reverse(width(&words[*i])));
+    for i in longest_first {
+        if width(&words.join(" ")) <= columns {
+            break;
+        }
+        if let Some(short) = shorten_path(&words[i]) {
+            words[i] = short;
+        }
+    }
+    words.join(" ")
+}
+
+// lines cut to the terminal width get their paths shortened instead, when
+// that is enough for them to fit. runs once the prioritizers have seen the
+// lines as they were read
+pub fn shorten_cut_lines(lines: &mut Lines) {
+    let home = std::env::var("HOME").ok();
+    for line in lines.lines.iter_mut() {
+        let Some(uncut) = &line.uncut else {
+            continue;
+        };
+        let short = shorten_paths(uncut, lines.columns, home.as_deref());
+        if short.chars().count() <= lines.columns {
+            line.text = short;
+            line.uncut = None;
+        }
+    }
+}
+
+const DIRECTIVE_OSC: &str = "\u{1b}]1337;oneless=";
+
+fn pin(word: &str) -> Option<Pin> {
+    match word {
+        "keep" => Some(Pin::Keep),
+        "drop" => Some(Pin::Drop),
+        _ => None,
+    }
+}
+
+// `\e]1337;oneless=keep\a`, terminals ignore it so it can go anywhere in
+// the line. returns the text without it
+fn osc_directive(text: &str) -> Option<(Pin, String)> {
+    let start = text.find(DIRECTIVE_OSC)?;
+    let rest = &text[start + DIRECTIVE_OSC.len()..];
+    let end = rest.find(['\u{7}', '\u{1b}'])?;
+    let terminator = if rest[end..].starts_with("\u{1b}\\") {
+        2
+    } else {
+        1
+    };
+    let pin = pin(&rest[..end])?;
+    Some((
+        pin,
+        format!("{}{}", &text[..start], &rest[end + terminator..]),
+    ))
+}
+
+// `echo hello # oneless:keep` with `--directive-prefix '# oneless:'`
+fn comment_directive(text: &str, prefix: &str) -> Option<(Pin, String)> {
+    let start = text.rfind(prefix)?;
+    let pin = pin(text[start + prefix.len()..].trim())?;
+    Some((pin, text[..start].trim_end().to_string()))
+}
+
+// lets the tool producing the input pin a line (`keep`) or give it up first
+// (`drop`), whatever the prioritizer thinks of it. read from the raw line,
+// before it is sanitized and cut to the terminal width, a directive past the
+// width being missed otherwise. returns the line without it
+pub fn take_directive(text: &str, comment_prefix: Option<&str>) -> Option<(Pin, String)> {
+    osc_directive(text).or_else(|| comment_prefix.and_then(|p| comment_directive(text, p)))
+}
+
+const BYTE_UNITS: [&str; 10] = [
+    "B", "kB", "KB", "KiB", "MB", "MiB", "GB", "GiB", "TB", "TiB",
+];
+
+// the line with every number replaced by `#`, so that successive states of a
+// counter compare equal
+pub fn counter_shape(text: &str) -> String {
+    let mut shape = String::with_capacity(text.len());
+    let mut in_number = false;
+    for c in text.chars() {
+        if c.is_ascii_digit() || in_number && (c == '.' || c == ',') {
+            if !in_number {
+                shape.push('#');
+            }
+            in_number = true;
+        } else {
+            shape.push(c);
+            in_number = false;
+        }
+    }
+    shape
+}
+
+// whether a number is followed by `%` or a byte unit, as in `37%`, `1.5 MiB`
+fn is_progress_unit(after: &str) -> bool {
+    let unit = after.strip_prefix(' ').unwrap_or(after);
+    after.starts_with('%')
+        || BYTE_UNITS.iter().any(|u| {
+            unit.strip_prefix(u)
+                .is_some_and(|r| !r.starts_with(|c: char| c.is_alphanumeric()))
+        })
+}
+
+// the line with only the numbers of percentages and sizes replaced by `#`,
+// none if there are no such numbers. `sda1 10 MB used` and `sda2 20 MB used`
+// still differ
+fn progress_shape(text: &str) -> Option<String> {
+    let mut shape = String::with_capacity(text.len());
+    let mut masked = false;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if !c.is_ascii_digit() {
+            shape.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(rest.len());
+        if is_progress_unit(&rest[end..]) {
+            shape.push('#');
+            masked = true;
+        } else {
+            shape.push_str(&rest[..end]);
+        }
+        rest = &rest[end..];
+    }
+    masked.then_some(shape)
+}
+
+// `Downloading 1%` ... `Downloading 100%` on successive lines becomes the
+// last of them
+pub fn fold_progress(lines: &mut Lines) {
+    let folded: Vec<Line> = lines
+        .lines
+        .chunk_by(|a, b| {
+            progress_shape(&a.text).is_some_and(|s| Some(s) == progress_shape(&b.text))
+        })
+        .map(|run| {
+            let mut line = run[run.len() - 1].clone();
+            line.pin = [Pin::Keep, Pin::Drop]
+                .into_iter()
+                .find(|p| run.iter().any(|l| l.pin == Some(*p)));
+            line.head = run.iter().find_map(|l| l.head);
+            line
+        })
+        .collect();
+    lines.lines = folded;
+}
+
+// `Total: 12`, `Summary`, `3 passed, 1 failed`, `test result: ok. ...`
+const SUMMARY_PATTERNS: [&str; 4] = [
+    r"(?i)^\W*(total|totals|summary|overall)\b",
+    r"(?i)\b\d+ (passed|failed|succeeded|skipped|errors?|warnings?)\b",
+    r"^test result:",
+    r"(?i)^(ran|executed) \d+ tests?\b",
+];
+// how far from the end, in non blank lines, a summary can be
+const MAX_SUMMARY_DISTANCE: usize = 5;
+
+// the summary lines tools end with are kept whatever the prioritizer thinks,
+// unless a directive decided otherwise
+pub fn pin_summaries(lines: &mut Lines) -> Result<()> {
+    let patterns = SUMMARY_PATTERNS
+        .iter()
+        .map(|p| Regex::new(p).with_context(|| format!("invalid pattern: {}", p)))
+        .collect::<Result<Vec<Regex>>>()?;
+    let trailing = lines
+        .lines
+        .iter_mut()
+        .rev()
+        .filter(|l| !l.text.trim().is_empty())
+        .take(MAX_SUMMARY_DISTANCE);
+    for line in trailing {
+        if line.pin.is_none() && patterns.iter().any(|p| p.is_match(&line.text)) {
+            line.pin = Some(Pin::Keep);
+        }
+    }
+    Ok(())
+}
+
+// what starts a record with `--record-separator`: a line after blank lines,
+// or a line matching a pattern (a timestamp, `commit `, ...)
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecordSeparator {
+    Blank,
+    Regex(String),
+}
+impl FromStr for RecordSeparator {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(':') {
+            _ if s == "blank" => Ok(RecordSeparator::Blank),
+            Some(("regex", pattern)) => Ok(RecordSeparator::Regex(pattern.to_string())),
+            _ => bail!(
+                "unknown record separator: {} (expected blank or regex:PATTERN)",
+                s
+            ),
+        }
+    }
+}
+
+// every line joins the record started before it, so that records are kept or
+// dropped whole. blank lines stay with the record they end
+pub fn mark_records(lines: &mut Lines, separator: &RecordSeparator) -> Result<()> {
+    let pattern = match separator {
+        RecordSeparator::Blank => None,
+        RecordSeparator::Regex(p) => {
+            Some(Regex::new(p).with_context(|| format!("invalid pattern: {}", p))?)
+        }
+    };
+    let mut head = None;
+    let mut previous_blank = true;
+    for (line_number, line) in lines.lines.iter_mut().enumerate() {
+        let blank = line.text.trim().is_empty();
+        let starts_record = match &pattern {
+            Some(pattern) => pattern.is_match(&line.text),
+            None => previous_blank && !blank,
+        };
+        previous_blank = blank;
+        if starts_record || head.is_none() {
+            head = Some(line_number);
+        } else {
+            line.head = head;
+        }
+    }
+    Ok(())
+}
+
+// wrapped events longer than this are stack traces and the like, pruned
+// line by line as long as their first line stays
+const MAX_STICKY_CONTINUATIONS: usize = 3;
+// share of the lines at the left margin starting an event for the input to
+// be a log, whose indented lines continue events
+const MIN_EVENT_SHARE: f32 = 0.5;
+
+// lines after a trailing `\`, and indented lines below a log event, continue
+// the line that started it. they can't be kept without it, and short events
+// are kept or dropped whole
+pub fn mark_continuations(lines: &mut Lines) {
+    let margin: Vec<&str> = lines
+        .lines
+        .iter()
+        .map(|l| l.text.as_str())
+        .filter(|t| !t.starts_with(char::is_whitespace) && !t.is_empty())
+        .collect();
+    let n_events = margin.iter().filter(|t| starts_event(t)).count();
+    let is_log = n_events > 0 && n_events as f32 >= MIN_EVENT_SHARE * margin.len() as f32;
+    let mut starts: Vec<Option<usize>> = Vec::with_capacity(lines.lines.len());
+    let mut start = None;
+    let mut continued = false;
+    for (line_number, line) in lines.lines.iter().enumerate() {
+        let indented = line.text.starts_with(char::is_whitespace) && !line.text.trim().is_empty();
+        if !(continued || indented && start.is_some()) {
+            start = (is_log && starts_event(&line.text)).then_some(line_number);
+        }
+        starts.push(start.filter(|s| *s < line_number));
+        continued = line.text.ends_with('\\');
+        if continued && start.is_none() {
+            start = Some(line_number);
+        }
+    }
+    for (line_number, line) in lines.lines.iter_mut().enumerate() {
+        let Some(start) = starts[line_number] else {
+            continue;
+        };
+        let length = starts[start + 1..]
+            .iter()
+            .take_while(|s| **s == Some(start))
+            .count();
+        line.parent = line.parent.or(Some(start));
+        if length <= MAX_STICKY_CONTINUATIONS {
+            line.head = line.head.or(Some(start));
+        }
+    }
+}
+
+// `retrying in 1s` three times in a row becomes `retrying in 1s (×3)`. the
+// line left keeps the pins and heads of the ones folded into it, a `keep`
+// winning over a `drop`
+pub fn fold_duplicates(lines: &mut Lines) {
+    let folded: Vec<Line> = lines
+        .lines
+        .chunk_by(|a, b| a.text == b.text && !a.text.trim().is_empty())
+        .map(|run| {
+            let mut line = run[0].clone();
+            if run.len() > 1 {
+                line.annotate(&format!(" (×{})", run.len()), lines.columns);
+                line.pin = [Pin::Keep, Pin::Drop]
+                    .into_iter()
+                    .find(|p| run.iter().any(|l| l.pin == Some(*p)));
+                line.head = run.iter().find_map(|l| l.head);
+            }
+            line
+        })
+        .collect();
+    lines.lines = folded;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn sanitize_carriage_returns() {
+        expect_that!(
+            sanitize("Downloading 10%\rDownloading 55%\rDownloading 100%"),
+            eq("Downloading 100%")
+        );
+        expect_that!(sanitize("done\r"), eq("done"));
+        expect_that!(sanitize("working |\u{8}/\u{8}-\u{8}ok"), eq("working ok"));
+        expect_that!(
+            sanitize("\u{1b}[2K\u{1b}[1Abuilding \u{1b}[32mok\u{1b}[0m\u{1b}[?25h"),
+            eq("building \u{1b}[32mok\u{1b}[0m")
+        );
+    }
+
+    #[gtest]
+    fn inline_directives() -> Result<()> {
+        let c = Cursor::new(
+            "\u{1b}]1337;oneless=keep\u{7}important\nnoise # oneless:drop\nplain\nkept too\u{1b}]1337;oneless=keep\u{1b}\\\n",
+        );
+        let lines = Lines::from_reader_with_directives(c, 100, 20, Some("# oneless:"))?;
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(texts, eq(&vec!["important", "noise", "plain", "kept too"]));
+        let pins: Vec<Option<Pin>> = lines.lines.iter().map(|l| l.pin).collect();
+        expect_that!(
+            pins,
+            eq(&vec![
+                Some(Pin::Keep),
+                Some(Pin::Drop),
+                None,
+                Some(Pin::Keep)
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn records() -> Result<()> {
+        let c = Cursor::new("a\nb\n\nc\n\n\nd\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        mark_records(&mut lines, &"blank".parse()?)?;
+        let heads: Vec<Option<usize>> = lines.lines.iter().map(|l| l.head).collect();
+        expect_that!(
+            heads,
+            eq(&vec![None, Some(0), Some(0), None, Some(3), Some(3), None])
+        );
+
+        let c = Cursor::new("2025 start\n  detail\n2025 stop\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        mark_records(&mut lines, &"regex:^\\d{4} ".parse()?)?;
+        let heads: Vec<Option<usize>> = lines.lines.iter().map(|l| l.head).collect();
+        expect_that!(heads, eq(&vec![None, Some(0), None]));
+        expect_that!("lines".parse::<RecordSeparator>().is_err(), eq(true));
+        Ok(())
+    }
+
+    #[gtest]
+    fn shorten_long_paths() {
+        let line = "error in /home/u/projects/oneless/src/lines.rs:12";
+        expect_that!(shorten_paths(line, 80, None), eq(line));
+        expect_that!(
+            shorten_paths(line, 40, None),
+            eq("error in /home/u/…/src/lines.rs:12")
+        );
+        expect_that!(
+            shorten_paths(line, 40, Some("/home/u")),
+            eq("error in ~/projects/oneless/src/lines.rs:12")
+        );
+        expect_that!(
+            shorten_paths("see https://example.com/a/b/c/d/e/f", 20, None),
+            eq("see https://example.com/a/b/c/d/e/f")
+        );
+    }
+
+    #[gtest]
+    fn shorten_cut_lines_after_reading() -> Result<()> {
+        let c = Cursor::new(
+            "error in /srv/u/projects/oneless/src/lines.rs:12\nerror in /srv/u/projects/oneless/src/lines.rs:12\na sentence without paths, far too long for the terminal\n",
+        );
+        let mut lines = Lines::from_reader(c, 45, 20)?;
+        expect_that!(
+            lines.lines[0].text,
+            eq("error in /srv/u/projects/oneless/src/lines.rs")
+        );
+        fold_duplicates(&mut lines);
+        shorten_cut_lines(&mut lines);
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(
+            texts,
+            eq(&vec![
+                "error in /srv/u/…/src/lines.rs:12 (×2)",
+                "a sentence without paths, far too long for th"
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn pin_trailing_summaries() -> Result<()> {
+        let c = Cursor::new(
+            "3 passed early\nok\nok\nok\nok\nok\nok\n\n=== 3 passed, 1 failed in 0.2s ===\nTotal: 4\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        pin_summaries(&mut lines)?;
+        let pins: Vec<Option<Pin>> = lines.lines.iter().map(|l| l.pin).collect();
+        expect_that!(pins[0], none());
+        expect_that!(pins[6], none());
+        expect_that!(pins[8], some(eq(Pin::Keep)));
+        expect_that!(pins[9], some(eq(Pin::Keep)));
+        Ok(())
+    }
+
+    #[gtest]
+    fn continuations() -> Result<()> {
+        let c = Cursor::new(
+            "ERROR failed\n  because\nplain\n  indented\nrun a \\\n  --flag\nINFO big\n 1\n 2\n 3\n 4\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        mark_continuations(&mut lines);
+        let heads: Vec<Option<usize>> = lines.lines.iter().map(|l| l.head).collect();
+        let parents: Vec<Option<usize>> = lines.lines.iter().map(|l| l.parent).collect();
+        expect_that!(
+            heads,
+            eq(&vec![
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                Some(4),
+                None,
+                None,
+                None,
+                None,
+                None
+            ])
+        );
+        expect_that!(parents[1], some(eq(0)));
+        expect_that!(parents[3], none());
+        expect_that!(parents[10], some(eq(6)));
+        Ok(())
+    }
+
+    #[gtest]
+    fn no_continuations_outside_logs() -> Result<()> {
+        for text in [
+            "handler: error\n  retries: 3\nname: warn-on-fail\n  level: 1\n",
+            "Warning signs come first.\n  the first one\nThe rest follows\n  below\nThe end\n",
+        ] {
+            let mut lines = Lines::from_reader(Cursor::new(text), 100, 20)?;
+            mark_continuations(&mut lines);
+            expect_that!(lines.lines.iter().all(|l| l.head.is_none()), eq(true));
+            expect_that!(lines.lines.iter().all(|l| l.parent.is_none()), eq(true));
+        }
+        Ok(())
+    }
+
+    #[gtest]
+    fn directives_past_the_width() -> Result<()> {
+        let c = Cursor::new(format!(
+            "{} # oneless:keep\n{}\x1b]1337;oneless=drop\x07\n",
+            "a".repeat(30),
+            "b".repeat(30)
+        ));
+        let lines = Lines::from_reader_with_directives(c, 20, 20, Some("# oneless:"))?;
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(texts, eq(&vec!["a".repeat(20), "b".repeat(20)]));
+        expect_that!(lines.lines[0].pin, some(eq(Pin::Keep)));
+        expect_that!(lines.lines[1].pin, some(eq(Pin::Drop)));
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_progress_counters() -> Result<()> {
+        let c = Cursor::new(
+            "Downloading 1%\nDownloading 37%\nDownloading 100%\nReceived 1.5 MiB\nReceived 12 MiB\nstep 1\nstep 2\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        fold_progress(&mut lines);
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(
+            texts,
+            eq(&vec![
+                "Downloading 100%",
+                "Received 12 MiB",
+                "step 1",
+                "step 2"
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_progress_only_counters() -> Result<()> {
+        let c = Cursor::new(
+            "sda1 10 MB used\nsda2 20 MB used\nsda3 30 MB used\nstep 1 at 5%\nstep 2 at 9%\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        fold_progress(&mut lines);
+        expect_that!(lines.lines.len(), eq(5));
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_progress_keeps_pins() -> Result<()> {
+        let c = Cursor::new("Downloading 1%\nDownloading 37%\nDownloading 100%\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        lines.lines[0].pin = Some(Pin::Keep);
+        lines.lines[1].pin = Some(Pin::Drop);
+        fold_progress(&mut lines);
+        expect_that!(lines.lines.len(), eq(1));
+        expect_that!(lines.lines[0].pin, some(eq(Pin::Keep)));
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_consecutive_duplicates() -> Result<()> {
+        let c = Cursor::new("start\nretry\nretry\nretry\n\n\nretry\ndone\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        fold_duplicates(&mut lines);
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(
+            texts,
+            eq(&vec!["start", "retry (×3)", "", "", "retry", "done"])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_duplicates_fits_columns() -> Result<()> {
+        let c = Cursor::new("0123456789\n0123456789\n");
+        let mut lines = Lines::from_reader(c, 10, 20)?;
+        fold_duplicates(&mut lines);
+        expect_that!(lines.lines[0].text, eq("01234 (×2)"));
+        Ok(())
+    }
+
+    #[gtest]
+    fn fold_duplicates_keeps_pins() -> Result<()> {
+        let c = Cursor::new("retry\nretry\nretry\nok\nok\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        lines.lines[1].pin = Some(Pin::Drop);
+        lines.lines[2].pin = Some(Pin::Keep);
+        lines.lines[4].pin = Some(Pin::Drop);
+        fold_duplicates(&mut lines);
+        expect_that!(lines.lines[0].pin, some(eq(Pin::Keep)));
+        expect_that!(lines.lines[1].pin, some(eq(Pin::Drop)));
+        Ok(())
+    }
+}
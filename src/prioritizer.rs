@@ -1,5 +1,80 @@
-use crate::lines::Lines;
-use anyhow::{Error, Result, bail};
+use crate::config::{Config, FormatConfig};
+use crate::lines::{Lines, Pin};
+use crate::options::{Options, head_ratio};
+use anyhow::{Context, Error, Result, bail};
+use regex::Regex;
+
+mod git_log;
+mod log_level;
+mod recency;
+mod diagnostic;
+mod cargo_test;
+mod lint;
+mod jvm_stack;
+mod go_panic;
+mod node_stack;
+mod js_test;
+mod yaml;
+mod xml;
+mod markdown;
+mod csv;
+mod table;
+mod units;
+mod timestamp;
+mod du;
+mod ls;
+mod ps;
+mod sockets;
+mod lsof;
+mod kernel_log;
+mod systemd;
+mod docker;
+mod docker_build;
+mod kubectl;
+mod kubectl_describe;
+mod terraform;
+mod package_manager;
+mod js_install;
+mod pip;
+mod make;
+mod jvm_build;
+mod backtrace;
+mod strace;
+mod ping;
+mod traceroute;
+mod dns;
+mod curl;
+mod rsync;
+mod access_log;
+mod line_length;
+mod grep;
+mod find;
+mod tree;
+mod counts;
+mod cluster;
+mod rarity;
+mod paragraph;
+mod section;
+mod reference;
+mod ansi;
+mod query_plan;
+mod sql_table;
+mod env;
+mod history;
+mod openssl;
+mod ip_addr;
+mod mounts;
+mod firewall;
+mod hardware_health;
+mod ci_groups;
+mod score;
+mod prio_regex;
+mod prio_field;
+mod magnitude;
+
+pub use ci_groups::expose_section_marker;
+pub use line_length::LengthPreference;
+pub use ls::LsCriterion;
 
 #[derive(Clone, Debug)]
 enum Confidence {
@@ -24,23 +99,350 @@ pub trait Prioritizer {
     fn prioritize(&self, lines: &mut Lines) -> Result<()>;
 }
 
-pub fn auto_prioritize(lines: &mut Lines) -> Result<()> {
+// what prioritizers can be told, with `-p name:option=value` or in a
+// `[prioritizer.name]` table of the config
+const PRIORITIZER_OPTIONS: [(&str, &str); 2] = [
+    // share of the kept lines taken from the start, e.g. `70%`
+    ("head_and_tail", "head"),
+    // what splits a line into levels, guessed by default
+    ("path_depth", "sep"),
+];
+
+// the command line wins over the config
+fn prioritizer_option<'a>(
+    options: &'a Options,
+    config: &'a Config,
+    prioritizer: &str,
+    option: &str,
+) -> Option<&'a str> {
+    options
+        .prioritizer_options
+        .iter()
+        .rev()
+        .find(|(p, o, _)| p == prioritizer && o == option)
+        .map(|(_, _, value)| value.as_str())
+        .or_else(|| config.prioritizer_option(prioritizer, option))
+}
+
+fn check_prioritizer_options(options: &Options, config: &Config) -> Result<()> {
+    let from_config = config
+        .prioritizers
+        .iter()
+        .flat_map(|(p, os)| os.keys().map(move |o| (p.as_str(), o.as_str())));
+    let given = options
+        .prioritizer_options
+        .iter()
+        .map(|(p, o, _)| (p.as_str(), o.as_str()))
+        .chain(from_config);
+    for (prioritizer, option) in given {
+        if !PRIORITIZER_OPTIONS.contains(&(prioritizer, option)) {
+            let known: Vec<String> = PRIORITIZER_OPTIONS
+                .iter()
+                .map(|(p, o)| format!("{}:{}", p, o))
+                .collect();
+            bail!(
+                "unknown prioritizer option {}:{} (expected one of {})",
+                prioritizer,
+                option,
+                known.join(", ")
+            );
+        }
+    }
+    // checked like --head-ratio, also when --head-ratio overrides them
+    let head_ratios = options
+        .prioritizer_options
+        .iter()
+        .filter(|(p, o, _)| p == "head_and_tail" && o == "head")
+        .map(|(_, _, value)| ("-p head_and_tail:head", value.as_str()))
+        .chain(
+            config
+                .prioritizer_option("head_and_tail", "head")
+                .map(|value| ("prioritizer.head_and_tail.head", value)),
+        );
+    for (name, value) in head_ratios {
+        head_ratio(name, value)?;
+    }
+    Ok(())
+}
+
+pub fn auto_prioritize(lines: &mut Lines, options: &Options, config: &Config) -> Result<()> {
     // TODO: just take some lines as samples
     let sample_lines = lines.clone();
-    let head_and_tail_prioritizer = Box::new(HeadAndTail::new(&sample_lines));
+    check_prioritizer_options(options, config)?;
+    let head_ratio = match options.head_ratio {
+        Some(ratio) => ratio,
+        None => match prioritizer_option(options, config, "head_and_tail", "head") {
+            Some(value) => head_ratio("prioritizer.head_and_tail.head", value)?,
+            None => 0.5,
+        },
+    };
+    let head_and_tail_prioritizer = Box::new(HeadAndTail::new(&sample_lines, head_ratio));
 
-    let prioritizers: Vec<Box<dyn Prioritizer>> = vec![
-        Box::new(PathDepth::new(&sample_lines)),
-        Box::new(FirstAlnum::new(&sample_lines)),
-        head_and_tail_prioritizer,
+    let prioritizers: Vec<(&str, Box<dyn Prioritizer>)> = vec![
+        (
+            "path_depth",
+            Box::new(PathDepth::new(
+                &sample_lines,
+                prioritizer_option(options, config, "path_depth", "sep"),
+            )),
+        ),
+        ("first_alnum", Box::new(FirstAlnum::new(&sample_lines))),
+        ("head_and_tail", head_and_tail_prioritizer),
+        // generic heuristics come first so format specific ones win ties
+        ("rarity", Box::new(rarity::Rarity::new(&sample_lines))),
+        (
+            "paragraph",
+            Box::new(paragraph::Paragraph::new(&sample_lines)),
+        ),
+        ("section", Box::new(section::Section::new(&sample_lines))),
+        (
+            "reference",
+            Box::new(reference::References::new(
+                &sample_lines,
+                &options.reference_patterns,
+            )?),
+        ),
+        ("cluster", Box::new(cluster::Cluster::new(&sample_lines))),
+        ("ansi", Box::new(ansi::AnsiColor::new(&sample_lines))),
+        (
+            "magnitude",
+            Box::new(magnitude::Magnitude::new(&sample_lines)),
+        ),
+        ("git_log", Box::new(git_log::GitLog::new(&sample_lines))),
+        (
+            "log_level",
+            Box::new(log_level::LogLevel::new(&sample_lines)),
+        ),
+        ("recency", Box::new(recency::Recency::new(&sample_lines))),
+        (
+            "diagnostic",
+            Box::new(diagnostic::CompilerDiagnostic::new(&sample_lines)),
+        ),
+        (
+            "cargo_test",
+            Box::new(cargo_test::CargoTest::new(&sample_lines)),
+        ),
+        ("lint", Box::new(lint::Lint::new(&sample_lines))),
+        (
+            "jvm_stack",
+            Box::new(jvm_stack::JvmStackTrace::new(&sample_lines)),
+        ),
+        ("go_panic", Box::new(go_panic::GoPanic::new(&sample_lines))),
+        (
+            "node_stack",
+            Box::new(node_stack::NodeStackTrace::new(&sample_lines)),
+        ),
+        ("js_test", Box::new(js_test::JsTest::new(&sample_lines))),
+        ("yaml", Box::new(yaml::Yaml::new(&sample_lines))),
+        ("xml", Box::new(xml::Xml::new(&sample_lines))),
+        ("markdown", Box::new(markdown::Markdown::new(&sample_lines))),
+        (
+            "csv",
+            Box::new(csv::Csv::new(&sample_lines, options.outlier_column)),
+        ),
+        ("table", Box::new(table::AlignedTable::new(&sample_lines))),
+        ("du", Box::new(du::Du::new(&sample_lines))),
+        (
+            "ls",
+            Box::new(ls::Ls::new(&sample_lines, options.ls_criterion)),
+        ),
+        ("ps", Box::new(ps::Ps::new(&sample_lines))),
+        ("sockets", Box::new(sockets::Sockets::new(&sample_lines))),
+        ("lsof", Box::new(lsof::Lsof::new(&sample_lines))),
+        (
+            "kernel_log",
+            Box::new(kernel_log::KernelLog::new(&sample_lines)),
+        ),
+        ("systemd", Box::new(systemd::Systemd::new(&sample_lines))),
+        ("docker", Box::new(docker::Docker::new(&sample_lines))),
+        (
+            "docker_build",
+            Box::new(docker_build::DockerBuild::new(&sample_lines)),
+        ),
+        ("kubectl", Box::new(kubectl::Kubectl::new(&sample_lines))),
+        (
+            "kubectl_describe",
+            Box::new(kubectl_describe::KubectlDescribe::new(&sample_lines)),
+        ),
+        (
+            "terraform",
+            Box::new(terraform::Terraform::new(&sample_lines)),
+        ),
+        (
+            "package_manager",
+            Box::new(package_manager::PackageManager::new(&sample_lines)),
+        ),
+        (
+            "js_install",
+            Box::new(js_install::JsInstall::new(&sample_lines)),
+        ),
+        ("pip", Box::new(pip::Pip::new(&sample_lines))),
+        ("make", Box::new(make::Make::new(&sample_lines))),
+        (
+            "jvm_build",
+            Box::new(jvm_build::JvmBuild::new(&sample_lines)),
+        ),
+        (
+            "backtrace",
+            Box::new(backtrace::Backtrace::new(&sample_lines)),
+        ),
+        ("strace", Box::new(strace::Strace::new(&sample_lines))),
+        ("ping", Box::new(ping::Ping::new(&sample_lines))),
+        (
+            "traceroute",
+            Box::new(traceroute::Traceroute::new(&sample_lines)),
+        ),
+        ("dns", Box::new(dns::Dns::new(&sample_lines))),
+        ("curl", Box::new(curl::Curl::new(&sample_lines))),
+        ("rsync", Box::new(rsync::Rsync::new(&sample_lines))),
+        (
+            "access_log",
+            Box::new(access_log::AccessLog::new(&sample_lines)),
+        ),
+        ("grep", Box::new(grep::Grep::new(&sample_lines))),
+        ("find", Box::new(find::Find::new(&sample_lines))),
+        ("tree", Box::new(tree::Tree::new(&sample_lines))),
+        ("counts", Box::new(counts::Counts::new(&sample_lines))),
+        (
+            "query_plan",
+            Box::new(query_plan::QueryPlan::new(&sample_lines)),
+        ),
+        (
+            "sql_table",
+            Box::new(sql_table::SqlTable::new(&sample_lines)),
+        ),
+        ("env", Box::new(env::Env::new(&sample_lines))),
+        ("history", Box::new(history::History::new(&sample_lines))),
+        ("openssl", Box::new(openssl::Openssl::new(&sample_lines))),
+        ("ip_addr", Box::new(ip_addr::IpAddr::new(&sample_lines))),
+        ("mounts", Box::new(mounts::Mounts::new(&sample_lines))),
+        ("firewall", Box::new(firewall::Firewall::new(&sample_lines))),
+        (
+            "hardware_health",
+            Box::new(hardware_health::HardwareHealth::new(&sample_lines)),
+        ),
+        (
+            "ci_groups",
+            Box::new(ci_groups::CiGroups::new(&sample_lines)),
+        ),
     ];
 
-    let prioritizer = prioritizers
+    let (format, _) = prioritizers
         .iter()
-        .max_by(|p, q| u32::from(p.confidence()).cmp(&(u32::from(q.confidence()))))
+        .max_by(|(_, p), (_, q)| u32::from(p.confidence()).cmp(&(u32::from(q.confidence()))))
         .unwrap();
-    dbg!(prioritizer.confidence());
-    prioritizer.prioritize(lines)
+    // the config can swap the prioritizer of a detected format
+    let format_config = config.formats.get(*format).cloned().unwrap_or_default();
+    let name = format_config.prioritizer.as_deref().unwrap_or(format);
+    let Some((_, detected)) = prioritizers.iter().find(|(n, _)| *n == name) else {
+        bail!("unknown prioritizer for format {}: {}", format, name);
+    };
+    // a priority asked for on the command line beats any detected format, as
+    // does a share of head lines, which only head and tail has
+    let user = user_prioritizer(options)?;
+    let head_ratio_given = options.head_ratio.is_some()
+        || options
+            .prioritizer_options
+            .iter()
+            .any(|(p, o, _)| p == "head_and_tail" && o == "head");
+    let head_and_tail = prioritizers
+        .iter()
+        .find(|(n, _)| *n == "head_and_tail")
+        .map(|(_, p)| p)
+        .filter(|_| head_ratio_given);
+    let prioritizer = user.as_ref().or(head_and_tail).unwrap_or(detected);
+    prioritizer.prioritize(lines)?;
+    if options.invert {
+        invert(lines);
+    }
+    if let Some(preference) = options.line_length {
+        line_length::LineLength::new(preference).prioritize(lines)?;
+    }
+    pin_matching(lines, &format_config)?;
+    // inline directives override whatever the prioritizer decided
+    if lines.lines.iter().any(|l| l.pin.is_some()) {
+        for line in lines.lines.iter_mut() {
+            let rank = match line.pin {
+                Some(Pin::Keep) => 0,
+                None => 1,
+                Some(Pin::Drop) => 2,
+            };
+            line.prio.insert(0, rank);
+        }
+    }
+    Ok(())
+}
+
+// keeps what would be dropped first, and the other way round
+fn invert(lines: &mut Lines) {
+    for line in lines.lines.iter_mut() {
+        for p in line.prio.iter_mut() {
+            *p = u32::MAX - *p;
+        }
+    }
+}
+
+// lines matching the `keep` and `drop` patterns of the format, unless an
+// inline directive already decided
+fn pin_matching(lines: &mut Lines, format_config: &FormatConfig) -> Result<()> {
+    let compile = |patterns: &[String]| {
+        patterns
+            .iter()
+            .map(|p| Regex::new(p).with_context(|| format!("invalid pattern: {}", p)))
+            .collect::<Result<Vec<Regex>>>()
+    };
+    let keep = compile(&format_config.keep)?;
+    let drop = compile(&format_config.drop)?;
+    for line in lines.lines.iter_mut().filter(|l| l.pin.is_none()) {
+        if keep.iter().any(|r| r.is_match(&line.text)) {
+            line.pin = Some(Pin::Keep);
+        } else if drop.iter().any(|r| r.is_match(&line.text)) {
+            line.pin = Some(Pin::Drop);
+        }
+    }
+    Ok(())
+}
+
+fn user_prioritizer(options: &Options) -> Result<Option<Box<dyn Prioritizer>>> {
+    if let Some(expression) = &options.score {
+        return Ok(Some(Box::new(score::Score::new(
+            expression,
+            options.reverse,
+        )?)));
+    }
+    if let Some(pattern) = &options.prio_regex {
+        return Ok(Some(Box::new(prio_regex::PrioRegex::new(
+            pattern,
+            options.reverse,
+        )?)));
+    }
+    if let Some(field) = options.prio_field {
+        return Ok(Some(Box::new(prio_field::PrioField::new(
+            field,
+            options.delimiter.clone(),
+            options.numeric,
+            options.reverse,
+        ))));
+    }
+    Ok(None)
+}
+
+// the first line of a log event, which may continue on the lines below
+pub fn starts_event(text: &str) -> bool {
+    timestamp::leading_timestamp(text).is_some() || log_level::leading_severity(text).is_some()
+}
+
+// fraction (0.0 to 1.0) of the sample lines for which `predicate` holds
+fn share_of_lines<P: Fn(&str) -> bool>(sample_lines: &Lines, predicate: P) -> f32 {
+    if sample_lines.lines.is_empty() {
+        return 0.0;
+    }
+    let n_matching = sample_lines
+        .lines
+        .iter()
+        .filter(|l| predicate(&l.text))
+        .count();
+    n_matching as f32 / sample_lines.lines.len() as f32
 }
 
 pub struct Head {
@@ -67,22 +469,38 @@ impl Prioritizer for Head {
     }
 }
 
+// `head_percent` of the kept lines come from the start, the rest from the end
 pub struct HeadAndTail {
     confidence: Confidence,
+    head_percent: u64,
 }
 impl HeadAndTail {
-    fn new(sample_lines: &Lines) -> HeadAndTail {
+    fn new(sample_lines: &Lines, head_ratio: f64) -> HeadAndTail {
         HeadAndTail {
             confidence: Confidence::Medium,
+            head_percent: (head_ratio * 100.0).round().clamp(0.0, 100.0) as u64,
         }
     }
 }
 impl Prioritizer for HeadAndTail {
     fn prioritize(&self, lines: &mut Lines) -> Result<()> {
         let len: usize = lines.lines.len();
+        // a line `n` lines from the start ranks as one `n * tail / head`
+        // from the end, both kept to the smallest integers
+        let (head, tail) = (self.head_percent, 100 - self.head_percent);
+        let gcd = (1..=head.min(tail).max(1))
+            .rev()
+            .find(|d| head % d == 0 && tail % d == 0)
+            .unwrap_or(1);
         for (line_number, line) in &mut lines.lines.iter_mut().enumerate() {
-            line.prio
-                .push(line_number.min(len - line_number - 1) as u32);
+            let from_head = line_number as u64;
+            let from_tail = (len - line_number - 1) as u64;
+            let rank = match head {
+                0 => from_tail,
+                100 => from_head,
+                _ => (from_head * tail / gcd).min(from_tail * head / gcd),
+            };
+            line.prio.push(rank.min(u32::MAX as u64) as u32);
         }
         Ok(())
     }
@@ -92,33 +510,70 @@ impl Prioritizer for HeadAndTail {
     }
 }
 
-const SEPARATOR: &str = "/";
+// `C:\` or `c:/`
+fn has_drive_letter(text: &str) -> bool {
+    let bytes = text.trim_start().as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && b"\\/".contains(&bytes[2])
+}
+
+// tried in order when no separator is given: paths, windows paths, rust
+// modules and dotted identifiers
+const SEPARATORS: [&str; 4] = ["/", "\\", "::", "."];
 pub struct PathDepth {
     confidence: Confidence,
+    separator: String,
 }
 impl PathDepth {
-    fn new(sample_lines: &Lines) -> PathDepth {
+    fn new(sample_lines: &Lines, separator: Option<&str>) -> PathDepth {
         let n_lines = sample_lines.lines.iter().count();
-        let n_lines_with_separator = sample_lines
-            .lines
-            .iter()
-            .filter(|l| l.text.contains(SEPARATOR))
-            .count();
-        if n_lines_with_separator >= n_lines - 2 && n_lines > 2 {
-            PathDepth {
-                confidence: Confidence::Certain
-            }
-        } else {
-            PathDepth {
+        // dots end sentences too, so dotted lines only count as one word
+        let splits = |text: &str, separator: &str| match separator {
+            "." => text.contains('.') && !text.contains(char::is_whitespace),
+            "\\" => text.contains('\\') || has_drive_letter(text),
+            _ => text.contains(separator),
+        };
+        let mostly_split = |separator: &&str| {
+            let n_lines_with_separator = sample_lines
+                .lines
+                .iter()
+                .filter(|l| splits(&l.text, separator))
+                .count();
+            n_lines > 2 && n_lines_with_separator >= n_lines - 2
+        };
+        let candidates = separator.map_or(SEPARATORS.to_vec(), |s| vec![s]);
+        match candidates.into_iter().find(mostly_split) {
+            Some(found) => PathDepth {
+                confidence: if found == "." {
+                    Confidence::High
+                } else {
+                    Confidence::Certain
+                },
+                separator: found.to_string(),
+            },
+            None => PathDepth {
                 confidence: Confidence::Low,
-            }
+                separator: separator.unwrap_or(SEPARATORS[0]).to_string(),
+            },
         }
     }
 }
+impl PathDepth {
+    fn depth(&self, text: &str) -> u32 {
+        if self.separator != "\\" {
+            return text.split(self.separator.as_str()).count() as u32;
+        }
+        // windows takes either slash, `C:\` is the root of its drive and
+        // `\\server\share` of its share
+        text.trim_matches(['\\', '/']).split(['\\', '/']).count() as u32
+    }
+}
 impl Prioritizer for PathDepth {
     fn prioritize(&self, lines: &mut Lines) -> Result<()> {
         for line in &mut lines.lines.iter_mut() {
-            line.prio.push(line.text.split(SEPARATOR).count() as u32);
+            line.prio.push(self.depth(&line.text));
         }
         Ok(())
     }
@@ -133,15 +588,16 @@ pub struct FirstAlnum {
 }
 impl FirstAlnum {
     fn new(sample_lines: &Lines) -> FirstAlnum {
+        // tree(1) output has its own prioritizer, this is for plain indentation
         let n_lines = sample_lines.lines.iter().count();
-        let n_lines_with_separator = sample_lines
+        let n_indented_lines = sample_lines
             .lines
             .iter()
-            .filter(|l| l.text.contains("├") || l.text.contains("└") )
+            .filter(|l| l.text.starts_with(' ') || l.text.starts_with('\t'))
             .count();
-        if n_lines_with_separator >= n_lines - 2 && n_lines > 2 {
+        if n_indented_lines * 2 >= n_lines && n_lines > 2 {
             FirstAlnum {
-                confidence: Confidence::Certain
+                confidence: Confidence::High
             }
         } else {
             FirstAlnum {
@@ -180,6 +636,115 @@ mod tests {
         Lines::from_reader(c, 20, 20).unwrap()
     }
 
+    #[gtest]
+    fn path_depth_separators() -> Result<()> {
+        let c = Cursor::new("crate::lines\ncrate::lines::Line\ncrate::prioritizer\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = PathDepth::new(&lines, None);
+        expect_that!(p.separator, eq("::"));
+        expect_that!(u32::from(p.confidence()), eq(u32::from(Confidence::Certain)));
+        p.prioritize(&mut lines)?;
+        expect_that!(lines.lines[1].prio, eq(&vec![3]));
+        let c = Cursor::new("a.b\na.b.c\nb.c\nThe end. Really.\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        let p = PathDepth::new(&lines, None);
+        expect_that!(p.separator, eq("."));
+        expect_that!(u32::from(p.confidence()), eq(u32::from(Confidence::High)));
+        let p = PathDepth::new(&lines, Some("::"));
+        expect_that!(u32::from(p.confidence()), eq(u32::from(Confidence::Low)));
+        let lines = Lines::from_reader(Cursor::new(""), 100, 20)?;
+        expect_that!(PathDepth::new(&lines, None).separator, eq("/"));
+        Ok(())
+    }
+
+    #[gtest]
+    fn path_depth_windows() -> Result<()> {
+        let c = Cursor::new("C:\\\nC:\\Users\nC:\\Users\\me/src\n\\\\server\\share\\x\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = PathDepth::new(&lines, None);
+        expect_that!(p.separator, eq("\\"));
+        p.prioritize(&mut lines)?;
+        let depths: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(depths, eq(&vec![1, 2, 4, 3]));
+        expect_that!(has_drive_letter("d:/games"), eq(true));
+        expect_that!(has_drive_letter("note: x"), eq(false));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prioritizer_options() -> Result<()> {
+        let mut options = Options::default();
+        let config = Config::parse("[prioritizer.path_depth]\nsep = \".\"\n")?;
+        expect_that!(
+            prioritizer_option(&options, &config, "path_depth", "sep"),
+            some(eq("."))
+        );
+        options.prioritizer_options.push((
+            "path_depth".to_string(),
+            "sep".to_string(),
+            "::".to_string(),
+        ));
+        expect_that!(
+            prioritizer_option(&options, &config, "path_depth", "sep"),
+            some(eq("::"))
+        );
+        expect_that!(check_prioritizer_options(&options, &config).is_ok(), eq(true));
+        let config = Config::parse("[prioritizer.path_depth]\ndepth = \"2\"\n")?;
+        expect_that!(check_prioritizer_options(&options, &config).is_err(), eq(true));
+        let config = Config::parse("[prioritizer.path-depth]\nsep = \".\"\n")?;
+        expect_that!(check_prioritizer_options(&options, &config).is_ok(), eq(true));
+        let config = Config::parse("[prioritizer.head_and_tail]\nhead = \"150%\"\n")?;
+        expect_that!(check_prioritizer_options(&options, &config).is_err(), eq(true));
+        options.prioritizer_options.push((
+            "head_and_tail".to_string(),
+            "head".to_string(),
+            "150%".to_string(),
+        ));
+        expect_that!(
+            check_prioritizer_options(&options, &Config::default()).is_err(),
+            eq(true)
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn invert_priorities() -> Result<()> {
+        let mut lines = make_lines();
+        HeadAndTail::new(&lines, 1.0).prioritize(&mut lines)?;
+        invert(&mut lines);
+        expect_that!(lines.lines[2].prio, lt(&lines.lines[1].prio));
+        expect_that!(lines.lines[1].prio, lt(&lines.lines[0].prio));
+        Ok(())
+    }
+
+    #[gtest]
+    fn pin_matching_lines() -> Result<()> {
+        let mut lines = make_lines();
+        lines.lines[2].pin = Some(Pin::Drop);
+        let format_config = FormatConfig {
+            prioritizer: None,
+            keep: vec!["^s".to_string(), "^t".to_string()],
+            drop: vec!["^f".to_string()],
+        };
+        pin_matching(&mut lines, &format_config)?;
+        expect_that!(lines.lines[0].pin, some(eq(Pin::Drop)));
+        expect_that!(lines.lines[1].pin, some(eq(Pin::Keep)));
+        // inline directives win
+        expect_that!(lines.lines[2].pin, some(eq(Pin::Drop)));
+        expect_that!(
+            pin_matching(
+                &mut lines,
+                &FormatConfig {
+                    keep: vec!["(".to_string()],
+                    ..FormatConfig::default()
+                }
+            )
+            .is_err(),
+            eq(true)
+        );
+        Ok(())
+    }
+
     #[gtest]
     fn head_prioritizer() -> Result<()> {
         let mut lines = make_lines();
@@ -194,7 +759,7 @@ mod tests {
     #[gtest]
     fn head_and_tail_prioritizer() -> Result<()> {
         let mut lines = make_lines();
-        let p = HeadAndTail::new(&lines);
+        let p = HeadAndTail::new(&lines, 0.5);
         p.prioritize(&mut lines)?;
         expect_that!(&lines.lines[0].prio, eq(&vec![0]));
         expect_that!(&lines.lines[1].prio, eq(&vec![1]));
@@ -203,12 +768,65 @@ mod tests {
         Ok(())
     }
 
+    #[gtest]
+    fn head_and_tail_ratio() -> Result<()> {
+        let text: String = (0..10).map(|n| format!("{}\n", n)).collect();
+        let mut lines = Lines::from_reader(Cursor::new(text), 100, 3)?;
+        HeadAndTail::new(&lines, 0.3).prioritize(&mut lines)?;
+        // each line from the start counts 7, from the end 3
+        expect_that!(lines.lines[1].prio, eq(&vec![7]));
+        expect_that!(lines.lines[8].prio, eq(&vec![3]));
+        let mut lines = Lines::from_reader(Cursor::new("a\nb\nc\n"), 100, 3)?;
+        HeadAndTail::new(&lines, 0.0).prioritize(&mut lines)?;
+        expect_that!(lines.lines[0].prio, eq(&vec![2]));
+        expect_that!(lines.lines[2].prio, eq(&vec![0]));
+        let mut lines = Lines::from_reader(Cursor::new(""), 100, 3)?;
+        HeadAndTail::new(&lines, 0.5).prioritize(&mut lines)?;
+        Ok(())
+    }
+
+    #[gtest]
+    fn head_ratio_beats_detection() -> Result<()> {
+        let text: String = (0..30)
+            .map(|n| format!("INFO request {} handled\n", n))
+            .collect();
+        let config = Config::default();
+        let tail_only = |options: &Options| -> Result<Vec<String>> {
+            let mut lines = Lines::from_reader(Cursor::new(text.clone()), 100, 5)?;
+            auto_prioritize(&mut lines, options, &config)?;
+            lines.prune();
+            Ok(lines.rows().into_iter().map(|r| r.1).collect())
+        };
+        let expected = vec![
+            "...",
+            "INFO request 26 handled",
+            "INFO request 27 handled",
+            "INFO request 28 handled",
+            "INFO request 29 handled",
+        ];
+        let options = Options {
+            head_ratio: Some(0.0),
+            ..Options::default()
+        };
+        expect_that!(tail_only(&options)?, eq(&expected));
+        let options = Options {
+            prioritizer_options: vec![(
+                "head_and_tail".to_string(),
+                "head".to_string(),
+                "0%".to_string(),
+            )],
+            ..Options::default()
+        };
+        expect_that!(tail_only(&options)?, eq(&expected));
+        Ok(())
+    }
+
     #[gtest]
     fn path_depth_prioritizer() -> Result<()> {
         //                   0  1    2      3      4      5  6
         let c = Cursor::new("x\nx/x\nx/x/x\nx/x/y\nx/x/z\ny\ny/x\n");
         let mut lines = Lines::from_reader(c, 20, 20).unwrap();
-        let p = PathDepth::new(&lines);
+        let p = PathDepth::new(&lines, None);
         p.prioritize(&mut lines)?;
         expect_that!(&lines.lines[0].prio, eq(&vec![1]));
         expect_that!(&lines.lines[1].prio, eq(&vec![2]));
@@ -0,0 +1,103 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_SERVER_ERROR: u32 = 0;
+const TIER_CLIENT_ERROR: u32 = 1;
+const TIER_SLOW: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_OK: u32 = 4;
+
+// seconds, as logged by nginx's $request_time or apache's %T
+const SLOW_SECONDS: f64 = 1.0;
+
+struct Request {
+    status: u32,
+    seconds: Option<f64>,
+}
+
+// common/combined log format:
+// `1.2.3.4 - - [16/Oct/2026:10:00:00 +0000] "GET / HTTP/1.1" 200 612 "-" "curl/8.5.0" 0.004`
+fn request(text: &str) -> Option<Request> {
+    let (_, rest) = text.split_once("] \"")?;
+    let (_, rest) = rest.split_once("\" ")?;
+    let status_word = rest.split_whitespace().next()?;
+    if status_word.len() != 3 {
+        return None;
+    }
+    let status = status_word.parse().ok()?;
+    // a trailing request time comes after the quoted user agent, if any
+    let seconds = text
+        .rsplit_once(' ')
+        .filter(|(_, last)| last.contains('.'))
+        .and_then(|(_, last)| last.parse().ok());
+    Some(Request { status, seconds })
+}
+
+fn tier(text: &str) -> u32 {
+    match request(text) {
+        Some(r) if r.status >= 500 => TIER_SERVER_ERROR,
+        Some(r) if r.status >= 400 => TIER_CLIENT_ERROR,
+        Some(r) if r.seconds.is_some_and(|s| s >= SLOW_SECONDS) => TIER_SLOW,
+        Some(r) if r.status < 300 => TIER_OK,
+        _ => TIER_OTHER,
+    }
+}
+
+pub struct AccessLog {
+    confidence: Confidence,
+}
+impl AccessLog {
+    pub fn new(sample_lines: &Lines) -> AccessLog {
+        AccessLog {
+            confidence: if share_of_lines(sample_lines, |t| request(t).is_some()) >= 0.6 {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for AccessLog {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tier(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const ACCESS_LOG: &str = r#"1.2.3.4 - - [16/Oct/2026:10:00:00 +0000] "GET / HTTP/1.1" 200 612 "-" "curl/8.5.0" 0.004
+1.2.3.4 - - [16/Oct/2026:10:00:01 +0000] "GET /missing HTTP/1.1" 404 153 "-" "curl/8.5.0" 0.001
+1.2.3.4 - - [16/Oct/2026:10:00:02 +0000] "POST /api HTTP/1.1" 502 166 "-" "curl/8.5.0" 0.010
+1.2.3.4 - - [16/Oct/2026:10:00:03 +0000] "GET /report HTTP/1.1" 200 9000 "-" "curl/8.5.0" 3.250
+1.2.3.4 - - [16/Oct/2026:10:00:04 +0000] "GET /old HTTP/1.1" 301 0 "-" "curl/8.5.0"
+"#;
+
+    #[gtest]
+    fn access_log_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(ACCESS_LOG), 100, 20)?;
+        let p = AccessLog::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_OK, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_CLIENT_ERROR, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_SERVER_ERROR, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_SLOW, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_OTHER, 4]));
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_RED_BOLD: u32 = 0;
+const TIER_RED: u32 = 1;
+const TIER_YELLOW: u32 = 2;
+const TIER_BOLD: u32 = 3;
+const TIER_COLORED: u32 = 4;
+const TIER_PLAIN: u32 = 5;
+
+// when everything is colored, color says nothing
+const MAX_COLORED_SHARE: f32 = 0.8;
+
+// xterm 256 color palette entries that look red or yellow
+const RED_256: [u32; 6] = [1, 9, 88, 124, 160, 196];
+const YELLOW_256: [u32; 8] = [3, 11, 178, 184, 214, 220, 226, 227];
+
+#[derive(Default)]
+struct Style {
+    bold: bool,
+    red: bool,
+    yellow: bool,
+    colored: bool,
+}
+
+fn rgb_style(style: &mut Style, r: u32, g: u32, b: u32) {
+    style.colored = true;
+    style.red |= r > 180 && g < 100 && b < 100;
+    style.yellow |= r > 180 && g > 150 && b < 100;
+}
+
+// every `ESC [ ... m` (SGR) sequence of the line, `0` resets are ignored
+fn style(text: &str) -> Style {
+    let mut style = Style::default();
+    for sequence in text.split("\x1b[").skip(1) {
+        let Some((codes, _)) = sequence.split_once('m') else {
+            continue;
+        };
+        let codes: Vec<u32> = codes.split(';').filter_map(|c| c.parse().ok()).collect();
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                1 => style.bold = true,
+                31 | 91 | 41 | 101 => {
+                    style.red = true;
+                    style.colored = true;
+                }
+                33 | 93 | 43 | 103 => {
+                    style.yellow = true;
+                    style.colored = true;
+                }
+                30..=37 | 90..=97 | 40..=47 | 100..=107 => style.colored = true,
+                38 | 48 => match codes.get(i + 1) {
+                    Some(5) => {
+                        let n = codes.get(i + 2).copied().unwrap_or(0);
+                        style.colored = true;
+                        style.red |= RED_256.contains(&n);
+                        style.yellow |= YELLOW_256.contains(&n);
+                        i += 2;
+                    }
+                    Some(2) => {
+                        let channel = |k: usize| codes.get(i + k).copied().unwrap_or(0);
+                        rgb_style(&mut style, channel(2), channel(3), channel(4));
+                        i += 4;
+                    }
+                    _ => (),
+                },
+                _ => (),
+            }
+            i += 1;
+        }
+    }
+    style
+}
+
+fn tier(text: &str) -> u32 {
+    let style = style(text);
+    if style.red && style.bold {
+        TIER_RED_BOLD
+    } else if style.red {
+        TIER_RED
+    } else if style.yellow {
+        TIER_YELLOW
+    } else if style.bold {
+        TIER_BOLD
+    } else if style.colored {
+        TIER_COLORED
+    } else {
+        TIER_PLAIN
+    }
+}
+
+pub struct AnsiColor {
+    confidence: Confidence,
+}
+impl AnsiColor {
+    pub fn new(sample_lines: &Lines) -> AnsiColor {
+        let has_signal = sample_lines
+            .lines
+            .iter()
+            .any(|l| tier(&l.text) <= TIER_YELLOW);
+        let colored_share = share_of_lines(sample_lines, |t| tier(t) < TIER_PLAIN);
+        AnsiColor {
+            confidence: if has_signal && colored_share <= MAX_COLORED_SHARE {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for AnsiColor {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tier(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const COLORED: &str = "plain output
+\x1b[1;31mfatal: could not connect\x1b[0m
+\x1b[31mretrying\x1b[0m
+\x1b[38;5;220mdeprecated flag\x1b[0m
+\x1b[1mSummary\x1b[0m
+\x1b[32mok\x1b[0m
+\x1b[38;2;255;0;0mtruecolor red\x1b[0m
+more plain output
+";
+
+    #[gtest]
+    fn ansi_color_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(COLORED), 100, 20)?;
+        let p = AnsiColor::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_PLAIN,
+                TIER_RED_BOLD,
+                TIER_RED,
+                TIER_YELLOW,
+                TIER_BOLD,
+                TIER_COLORED,
+                TIER_RED,
+                TIER_PLAIN,
+            ])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_SIGNAL: u32 = 0;
+const TIER_FAULTING: u32 = 1;
+const TIER_USER: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_RUNTIME: u32 = 4;
+
+// frames inside these are rarely where the bug is
+const RUNTIME_MARKERS: [&str; 10] = [
+    "libc.so",
+    "libc-",
+    "libpthread",
+    "ld-linux",
+    "/sysdeps/",
+    "libsystem_",
+    "libdyld",
+    "libc++",
+    "libstdc++",
+    "glibc",
+];
+
+// gdb's `#3  0x000055555555518d in main () at main.c:12`, lldb's
+// `  * frame #0: 0x0000000100003f50 a.out`main at main.c:5:3`
+fn frame_number(text: &str) -> Option<u32> {
+    let trimmed = text.trim_start().trim_start_matches("* ");
+    let rest = trimmed
+        .strip_prefix("frame #")
+        .or_else(|| trimmed.strip_prefix('#'))?;
+    let end = rest.find(|c: char| !c.is_ascii_digit())?;
+    // buildkit also numbers its lines with `#N `, frames carry an address or a location
+    let frame = rest[end..].trim_start_matches([' ', ':']);
+    if end == 0 || !(frame.starts_with("0x") || frame.contains(") at ")) {
+        return None;
+    }
+    rest[..end].parse().ok()
+}
+
+fn is_signal(text: &str) -> bool {
+    text.contains("received signal ") || text.contains("stop reason = ")
+}
+
+fn is_runtime_frame(text: &str) -> bool {
+    let function = text
+        .split_once(" in ")
+        .map_or(text, |(_, f)| f)
+        .trim_start();
+    RUNTIME_MARKERS.iter().any(|m| text.contains(m))
+        || function.starts_with("__")
+        || function.starts_with("_start")
+        || function.starts_with("raise ")
+        || function.starts_with("abort ")
+        || text.contains(" from /lib")
+        || text.contains(" from /usr/lib")
+}
+
+pub struct Backtrace {
+    confidence: Confidence,
+}
+impl Backtrace {
+    pub fn new(sample_lines: &Lines) -> Backtrace {
+        let n_frames = sample_lines
+            .lines
+            .iter()
+            .filter(|l| frame_number(&l.text).is_some())
+            .count();
+        Backtrace {
+            confidence: if n_frames >= 2 && n_frames * 3 >= sample_lines.lines.len() {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Backtrace {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match frame_number(&line.text) {
+                _ if is_signal(&line.text) => TIER_SIGNAL,
+                Some(0) => TIER_FAULTING,
+                Some(_) if is_runtime_frame(&line.text) => TIER_RUNTIME,
+                Some(_) => TIER_USER,
+                None => TIER_OTHER,
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const GDB: &str = "Program received signal SIGABRT, Aborted.
+(gdb) bt
+#0  __pthread_kill_implementation (no_tid=0, signo=6) at ./nptl/pthread_kill.c:44
+#1  0x00007ffff7c42476 in __GI_raise (sig=sig@entry=6) at ../sysdeps/posix/raise.c:26
+#2  0x0000555555555189 in check (p=0x0) at main.c:7
+#3  0x00005555555551a4 in main () at main.c:12
+";
+
+    #[gtest]
+    fn backtrace_gdb() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(GDB), 100, 20)?;
+        let p = Backtrace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_SIGNAL, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_OTHER, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FAULTING, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_RUNTIME, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_USER, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_USER, 5]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn backtrace_lldb() -> Result<()> {
+        let c = Cursor::new(
+            "* thread #1, stop reason = EXC_BAD_ACCESS (code=1, address=0x0)\n  * frame #0: 0x0000000100003f50 a.out`main at main.c:5:3\n    frame #1: 0x00007fff6c4d3cc9 libdyld.dylib`start + 1\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Backtrace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_SIGNAL, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FAULTING, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_RUNTIME, 2]));
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_FAILURE: u32 = 0;
+const TIER_FAILURE_OUTPUT: u32 = 1;
+const TIER_STRUCTURE: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_PASSED: u32 = 4;
+
+fn is_test_line(text: &str) -> bool {
+    text.starts_with("test ") && text.contains(" ... ")
+}
+
+fn is_summary(text: &str) -> bool {
+    text.starts_with("test result: ")
+}
+
+fn is_failure_output_header(text: &str) -> bool {
+    text.starts_with("---- ") && text.ends_with(" ----")
+}
+
+pub struct CargoTest {
+    confidence: Confidence,
+}
+impl CargoTest {
+    pub fn new(sample_lines: &Lines) -> CargoTest {
+        let has_test_line = sample_lines.lines.iter().any(|l| is_test_line(&l.text));
+        let has_summary = sample_lines.lines.iter().any(|l| {
+            is_summary(&l.text) || (l.text.starts_with("running ") && l.text.ends_with(" tests"))
+        });
+        CargoTest {
+            confidence: if has_test_line && has_summary {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for CargoTest {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // the failures section is printed twice: once with the captured
+        // output of every failed test, once as a plain list of names
+        let mut in_failure_output = false;
+        let mut in_failure_list = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = line.text.trim_end();
+            let tier = if is_summary(text) {
+                in_failure_output = false;
+                in_failure_list = false;
+                TIER_FAILURE
+            } else if is_test_line(text) {
+                if text.ends_with("FAILED") {
+                    TIER_FAILURE
+                } else {
+                    TIER_PASSED
+                }
+            } else if text == "failures:" {
+                in_failure_list = in_failure_output;
+                in_failure_output = !in_failure_output;
+                TIER_STRUCTURE
+            } else if is_failure_output_header(text) || text.contains("panicked at") {
+                TIER_FAILURE
+            } else if in_failure_output {
+                TIER_FAILURE_OUTPUT
+            } else if in_failure_list || text.starts_with("running ") || text.contains("Running ") {
+                TIER_STRUCTURE
+            } else {
+                TIER_OTHER
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lines::LineStatus;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const CARGO_TEST: &str = "
+running 4 tests
+test tests::one ... ok
+test tests::two ... FAILED
+test tests::three ... ok
+test tests::four ... ignored
+
+failures:
+
+---- tests::two stdout ----
+thread 'tests::two' panicked at src/lib.rs:10:5:
+assertion `left == right` failed
+  left: 1
+ right: 2
+
+failures:
+    tests::two
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out
+";
+
+    #[gtest]
+    fn cargo_test_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(CARGO_TEST), 80, 20)?;
+        let p = CargoTest::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PASSED, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_FAILURE, 3]));
+        expect_that!(&lines.lines[9].prio, eq(&vec![TIER_FAILURE, 9]));
+        expect_that!(&lines.lines[10].prio, eq(&vec![TIER_FAILURE, 10]));
+        expect_that!(&lines.lines[11].prio, eq(&vec![TIER_FAILURE_OUTPUT, 11]));
+        expect_that!(&lines.lines[16].prio, eq(&vec![TIER_STRUCTURE, 16]));
+        expect_that!(&lines.lines[18].prio, eq(&vec![TIER_FAILURE, 18]));
+
+        lines.target_lines = 8;
+        lines.prune();
+        expect_that!(lines.lines[3].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[10].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[18].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[4].status, not(eq(&LineStatus::Kept)));
+        Ok(())
+    }
+}
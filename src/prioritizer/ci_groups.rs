@@ -0,0 +1,210 @@
+use super::log_level::{SEVERITY_ERROR, severity};
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+use std::borrow::Cow;
+
+const TIER_TITLE: u32 = 0;
+const TIER_ERROR: u32 = 1;
+const TIER_FAILED_GROUP: u32 = 2;
+const TIER_UNGROUPED: u32 = 3;
+const TIER_FOLDED: u32 = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Fold {
+    Start,
+    End,
+}
+
+// github actions prefixes every line of a downloaded log with
+// `2026-10-16T10:00:00.1234567Z `
+fn strip_timestamp(text: &str) -> &str {
+    match text.split_once(' ') {
+        Some((first, rest)) if first.len() > 20 && first.ends_with('Z') && first.contains('T') => {
+            rest
+        }
+        _ => text,
+    }
+}
+
+// github's `::group::`/`##[group]`, azure's `##[section]`, gitlab's
+// `section_start:1560896352:build_script`
+fn group_marker(text: &str) -> Option<Fold> {
+    let text = strip_timestamp(text);
+    if text.starts_with("::group::")
+        || text.starts_with("##[group]")
+        || text.starts_with("##[section]")
+        || text.contains("section_start:")
+    {
+        Some(Fold::Start)
+    } else if text.starts_with("::endgroup::")
+        || text.starts_with("##[endgroup]")
+        || text.contains("section_end:")
+    {
+        Some(Fold::End)
+    } else {
+        None
+    }
+}
+
+// gitlab hides its section markers behind the section title, going back
+// over them with `\r\e[0K`. moved in front of the title while the raw line
+// is read, so that resolving the carriage return keeps them
+pub fn expose_section_marker(text: &str) -> Cow<'_, str> {
+    match text.split_once('\r') {
+        Some((marker, title)) if marker.starts_with("section_") => {
+            match title.trim_start_matches("\u{1b}[0K") {
+                "" => Cow::Borrowed(marker),
+                title => Cow::Owned(format!("{} {}", marker, title)),
+            }
+        }
+        _ => Cow::Borrowed(text),
+    }
+}
+
+fn is_error(text: &str) -> bool {
+    let text = strip_timestamp(text);
+    text.starts_with("##[error]")
+        || text.starts_with("::error")
+        || severity(text).is_some_and(|s| s <= SEVERITY_ERROR)
+}
+
+pub struct CiGroups {
+    confidence: Confidence,
+}
+impl CiGroups {
+    pub fn new(sample_lines: &Lines) -> CiGroups {
+        let n_groups = sample_lines
+            .lines
+            .iter()
+            .filter(|l| group_marker(&l.text) == Some(Fold::Start))
+            .count();
+        CiGroups {
+            confidence: if n_groups > 0 {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for CiGroups {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // the group of every line, and whether each group saw an error
+        let mut groups: Vec<Option<usize>> = Vec::with_capacity(lines.lines.len());
+        let mut failed: Vec<bool> = Vec::new();
+        let mut current: Option<usize> = None;
+        for line in &lines.lines {
+            match group_marker(&line.text) {
+                Some(Fold::Start) => {
+                    failed.push(false);
+                    current = Some(failed.len() - 1);
+                    groups.push(current);
+                }
+                Some(Fold::End) => {
+                    groups.push(current);
+                    current = None;
+                }
+                None => {
+                    if let Some(group) = current
+                        && is_error(&line.text)
+                    {
+                        failed[group] = true;
+                    }
+                    groups.push(current);
+                }
+            }
+        }
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match groups[line_number] {
+                _ if group_marker(&line.text) == Some(Fold::Start) => TIER_TITLE,
+                _ if is_error(&line.text) => TIER_ERROR,
+                Some(group) if failed[group] => TIER_FAILED_GROUP,
+                Some(_) => TIER_FOLDED,
+                None => TIER_UNGROUPED,
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const ACTIONS: &str = "##[group]Run actions/checkout@v4
+Syncing repository
+##[endgroup]
+##[group]Run cargo test
+running 3 tests
+##[error]Process completed with exit code 101.
+##[endgroup]
+Post job cleanup.
+";
+
+    #[gtest]
+    fn ci_groups_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(ACTIONS), 100, 20)?;
+        let p = CiGroups::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_TITLE,
+                TIER_FOLDED,
+                TIER_FOLDED,
+                TIER_TITLE,
+                TIER_FAILED_GROUP,
+                TIER_ERROR,
+                TIER_FAILED_GROUP,
+                TIER_UNGROUPED,
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn ci_groups_timestamps() -> Result<()> {
+        let c = Cursor::new(
+            "2026-10-16T10:00:00.1234567Z ::group::Install\n2026-10-16T10:00:01.1234567Z fetched 12 packages\n2026-10-16T10:00:02.1234567Z ::endgroup::\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        CiGroups::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_TITLE, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FOLDED, 1]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn ci_groups_gitlab_sections() -> Result<()> {
+        let c = Cursor::new(
+            "section_start:1560896352:build\r\u{1b}[0KBuild\ncompiling\nsection_end:1560896353:build\r\u{1b}[0K\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+        expect_that!(
+            texts,
+            eq(&vec![
+                "section_start:1560896352:build Build",
+                "compiling",
+                "section_end:1560896353:build"
+            ])
+        );
+        CiGroups::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_TITLE, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FOLDED, 1]));
+        Ok(())
+    }
+}
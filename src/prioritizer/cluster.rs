@@ -0,0 +1,224 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// lines whose fingerprints differ in at most this many bits are near
+// duplicates. another word in a line of a few words flips 5 to 20 bits, the
+// fingerprints of unrelated lines differ in about 32
+const MAX_DISTANCE: u32 = 20;
+// clusters smaller than this are not worth a line standing for them
+const MIN_LINES_PER_CLUSTER: usize = 3;
+// share of the lines in such clusters for clustering to be worth it
+const MIN_CLUSTERED_SHARE: f64 = 0.6;
+const MIN_LINES: usize = 10;
+
+// digits are masked so `host-01` and `host-42` are the same word
+pub fn tokens(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.chars()
+                .map(|c| if c.is_ascii_digit() { '0' } else { c })
+                .collect()
+        })
+        .collect()
+}
+
+// the words of a line with every number as `0`, however many digits it has
+pub fn template(text: &str) -> Vec<String> {
+    tokens(text)
+        .into_iter()
+        .map(|w| {
+            let mut word = String::with_capacity(w.len());
+            for c in w.chars() {
+                if !(c == '0' && word.ends_with('0')) {
+                    word.push(c);
+                }
+            }
+            word
+        })
+        .collect()
+}
+
+// simhash over words and pairs of consecutive words (shingles)
+fn fingerprint(text: &str) -> u64 {
+    let tokens = tokens(text);
+    let mut weights = [0i32; 64];
+    let shingles = tokens.windows(2).map(|w| w.join(" "));
+    for feature in tokens.iter().cloned().chain(shingles) {
+        let mut hasher = DefaultHasher::new();
+        feature.hash(&mut hasher);
+        let hash = hasher.finish();
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            *weight += if hash >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | 1 << bit)
+}
+
+// index of the cluster of each line, clusters numbered in order of appearance
+fn clusters(lines: &Lines) -> Vec<usize> {
+    let mut representatives: Vec<u64> = Vec::new();
+    lines
+        .lines
+        .iter()
+        .map(|line| {
+            let fingerprint = fingerprint(&line.text);
+            match representatives
+                .iter()
+                .position(|r| (r ^ fingerprint).count_ones() <= MAX_DISTANCE)
+            {
+                Some(cluster) => cluster,
+                None => {
+                    representatives.push(fingerprint);
+                    representatives.len() - 1
+                }
+            }
+        })
+        .collect()
+}
+
+pub struct Cluster {
+    confidence: Confidence,
+}
+impl Cluster {
+    // lines only count as near duplicates in clusters with words varying
+    // from line to line. when only the digits change (`line 1`, `line 2`,
+    // ...) the lines are a numbered listing rather than repetitions
+    pub fn new(sample_lines: &Lines) -> Cluster {
+        let n_lines = sample_lines.lines.len();
+        let clusters = clusters(sample_lines);
+        let mut templates: Vec<Vec<Vec<String>>> = Vec::new();
+        let mut sizes: Vec<usize> = Vec::new();
+        for (line, cluster) in sample_lines.lines.iter().zip(&clusters) {
+            if *cluster == sizes.len() {
+                templates.push(Vec::new());
+                sizes.push(0);
+            }
+            sizes[*cluster] += 1;
+            let template = template(&line.text);
+            if !templates[*cluster].contains(&template) {
+                templates[*cluster].push(template);
+            }
+        }
+        let n_clustered: usize = (0..sizes.len())
+            .filter(|c| sizes[*c] >= MIN_LINES_PER_CLUSTER && templates[*c].len() > 1)
+            .map(|c| sizes[c])
+            .sum();
+        Cluster {
+            confidence: if n_lines >= MIN_LINES
+                && n_clustered as f64 >= MIN_CLUSTERED_SHARE * n_lines as f64
+            {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Cluster {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let clusters = clusters(lines);
+        let mut representatives: Vec<usize> = Vec::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let cluster = clusters[line_number];
+            // the first line of every cluster stands for the rest, saying how
+            // many of them were pruned
+            let representative = cluster == representatives.len();
+            if representative {
+                representatives.push(line_number);
+            } else {
+                line.similar_to = Some(representatives[cluster]);
+            }
+            line.prio.push(if representative { 0 } else { 1 });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    fn timeouts() -> String {
+        let mut input = String::from("starting client\n");
+        for (n, host) in ["web", "db", "cache", "queue"]
+            .iter()
+            .cycle()
+            .take(12)
+            .enumerate()
+        {
+            input += &format!(
+                "error: failed to connect to {}-{:02}: connection timed out after 30s\n",
+                host, n
+            );
+        }
+        input + "giving up\n"
+    }
+
+    #[gtest]
+    fn cluster_near_duplicates() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(timeouts()), 100, 4)?;
+        let p = Cluster::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![1, 2]));
+        expect_that!(lines.lines[2].similar_to, some(eq(1)));
+        expect_that!(&lines.lines[13].prio, eq(&vec![0, 13]));
+        lines.prune();
+        let rows: Vec<String> = lines.rows().into_iter().map(|r| r.1).collect();
+        expect_that!(
+            rows,
+            eq(&vec![
+                "starting client",
+                "error: failed to connect to web-00: connection timed out after 30s (+11 similar)",
+                "... 11 lines ...",
+                "giving up"
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn cluster_annotates_only_pruned() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(timeouts()), 100, 20)?;
+        Cluster::new(&lines).prioritize(&mut lines)?;
+        lines.prune();
+        let rows = lines.rows();
+        expect_that!(rows.len(), eq(14));
+        expect_that!(rows.iter().any(|r| r.1.contains("similar")), eq(false));
+        Ok(())
+    }
+
+    #[gtest]
+    fn cluster_numbered_lines() -> Result<()> {
+        let input: String = (1..=100).map(|n| format!("line {}\n", n)).collect();
+        let lines = Lines::from_reader(Cursor::new(input), 100, 5)?;
+        expect_that!(u32::from(Cluster::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+
+    #[gtest]
+    fn cluster_distinct_lines() -> Result<()> {
+        let c = Cursor::new("alpha\nbeta\ngamma\ndelta\nepsilon\nzeta\neta\ntheta\niota\nkappa\n");
+        let lines = Lines::from_reader(c, 100, 5)?;
+        expect_that!(u32::from(Cluster::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
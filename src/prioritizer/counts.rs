@@ -0,0 +1,79 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+// `     42 GET /index.html` from `sort | uniq -c`; du separates its sizes with
+// a tab instead
+fn count(text: &str) -> Option<u32> {
+    let (count, rest) = text.trim_start().split_once(' ')?;
+    if rest.trim().is_empty() {
+        return None;
+    }
+    count.parse().ok()
+}
+
+pub struct Counts {
+    confidence: Confidence,
+}
+impl Counts {
+    pub fn new(sample_lines: &Lines) -> Counts {
+        Counts {
+            // a header line (ps, top) means it is a table rather than a report
+            confidence: if sample_lines.lines.len() > 2
+                && sample_lines
+                    .lines
+                    .first()
+                    .is_some_and(|l| count(&l.text).is_some())
+                && share_of_lines(sample_lines, |t| count(t).is_some()) >= 0.9
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Counts {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // heavy hitters first
+            line.prio.push(u32::MAX - count(&line.text).unwrap_or(0));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn counts_prioritizer() -> Result<()> {
+        let c = Cursor::new("      3 apple\n   1200 banana\n     17 cherry\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Counts::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![u32::MAX - 3, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![u32::MAX - 1200, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![u32::MAX - 17, 2]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn counts_not_du_output() -> Result<()> {
+        let c = Cursor::new("4\t./a\n8\t./b\n12\t.\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Counts::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
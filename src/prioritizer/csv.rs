@@ -0,0 +1,190 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+const TIER_HEADER: u32 = 0;
+const TIER_OUTLIER: u32 = 1;
+const TIER_ROW: u32 = 2;
+
+// a value further than this many standard deviations from the mean
+const OUTLIER_DEVIATIONS: f64 = 2.0;
+
+// splits on `delimiter` outside of double quotes
+fn split_fields(text: &str, delimiter: char) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == delimiter && !in_quotes {
+            fields.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    fields.push(&text[start..]);
+    fields
+}
+
+// the delimiter giving the same number (at least 2) of fields on nearly all lines
+fn detect_delimiter(sample_lines: &Lines) -> Option<char> {
+    let rows: Vec<&str> = sample_lines
+        .lines
+        .iter()
+        .map(|l| l.text.as_str())
+        .filter(|t| !t.trim().is_empty())
+        .collect();
+    if rows.len() < 3 {
+        return None;
+    }
+    DELIMITERS.into_iter().find(|d| {
+        let n_fields = split_fields(rows[0], *d).len();
+        let n_consistent = rows
+            .iter()
+            .filter(|r| split_fields(r, *d).len() == n_fields)
+            .count();
+        n_fields >= 2 && n_consistent as f32 >= 0.9 * rows.len() as f32
+    })
+}
+
+fn outliers(values: &[Option<f64>]) -> Vec<bool> {
+    let numbers: Vec<f64> = values.iter().flatten().copied().collect();
+    if numbers.len() < 3 {
+        return vec![false; values.len()];
+    }
+    let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+    let variance = numbers.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / numbers.len() as f64;
+    let deviation = variance.sqrt();
+    values
+        .iter()
+        .map(|v| {
+            v.is_some_and(|x| deviation > 0.0 && (x - mean).abs() > OUTLIER_DEVIATIONS * deviation)
+        })
+        .collect()
+}
+
+pub struct Csv {
+    confidence: Confidence,
+    delimiter: char,
+    outlier_column: Option<usize>,
+}
+impl Csv {
+    // `outlier_column` counts from 1, like `cut -f`
+    pub fn new(sample_lines: &Lines, outlier_column: Option<usize>) -> Csv {
+        match detect_delimiter(sample_lines) {
+            Some(delimiter) => Csv {
+                confidence: Confidence::High,
+                delimiter,
+                outlier_column,
+            },
+            None => Csv {
+                confidence: Confidence::Low,
+                delimiter: ',',
+                outlier_column,
+            },
+        }
+    }
+}
+impl Prioritizer for Csv {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let header = lines.lines.iter().position(|l| !l.text.trim().is_empty());
+        let values: Vec<Option<f64>> = lines
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(line_number, l)| {
+                let column = self.outlier_column?;
+                if Some(line_number) == header {
+                    return None;
+                }
+                let fields = split_fields(&l.text, self.delimiter);
+                fields
+                    .get(column - 1)?
+                    .trim()
+                    .trim_matches('"')
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        let outliers = outliers(&values);
+        let len: usize = lines.lines.len();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = if Some(line_number) == header {
+                TIER_HEADER
+            } else if outliers[line_number] {
+                TIER_OUTLIER
+            } else {
+                TIER_ROW
+            };
+            line.prio.push(tier);
+            line.prio
+                .push(line_number.min(len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const CSV: &str = "name,\"size, in kB\",owner
+a,10,me
+b,12,me
+c,11,you
+d,9000,me
+e,10,you
+f,13,me
+g,11,you
+";
+
+    #[gtest]
+    fn csv_fields() {
+        expect_that!(
+            split_fields("a,\"b,c\",d", ','),
+            eq(&vec!["a", "\"b,c\"", "d"])
+        );
+        expect_that!(split_fields("a\tb", '\t'), eq(&vec!["a", "b"]));
+    }
+
+    #[gtest]
+    fn csv_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(CSV), 100, 20)?;
+        let p = Csv::new(&lines, None);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        expect_that!(p.delimiter, eq(','));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ROW, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ROW, 3]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_ROW, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn csv_outliers() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(CSV), 100, 20)?;
+        let p = Csv::new(&lines, Some(2));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ROW, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_OUTLIER, 3]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prose_is_not_csv() {
+        let c = Cursor::new("hello, world\nno commas here\nwell, maybe, some\n");
+        let lines = Lines::from_reader(c, 100, 20).unwrap();
+        expect_that!(u32::from(Csv::new(&lines, None).confidence()), eq(10));
+    }
+}
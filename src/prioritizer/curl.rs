@@ -0,0 +1,145 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_STATUS: u32 = 0;
+const TIER_HEADER: u32 = 1;
+const TIER_CONNECTION: u32 = 2;
+const TIER_OTHER_HEADER: u32 = 3;
+const TIER_BODY: u32 = 4;
+const TIER_CHATTER: u32 = 5;
+
+const INTERESTING_HEADERS: [&str; 6] = [
+    "location",
+    "content-type",
+    "set-cookie",
+    "www-authenticate",
+    "retry-after",
+    "content-length",
+];
+
+// `> GET /path HTTP/1.1`
+fn is_request_line(text: &str) -> bool {
+    text.strip_prefix("> ")
+        .and_then(|r| r.split_whitespace().last())
+        .is_some_and(|w| w.starts_with("HTTP/"))
+}
+
+fn is_status_line(text: &str) -> bool {
+    text.starts_with("< HTTP/")
+}
+
+fn is_interesting_header(text: &str) -> bool {
+    let Some((name, _)) = text.get(2..).and_then(|h| h.split_once(':')) else {
+        return false;
+    };
+    INTERESTING_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+fn tier(text: &str) -> u32 {
+    if is_request_line(text) || is_status_line(text) || text.starts_with("curl: (") {
+        TIER_STATUS
+    } else if text.starts_with("> ") || text.starts_with("< ") {
+        if is_interesting_header(text) {
+            TIER_HEADER
+        } else {
+            TIER_OTHER_HEADER
+        }
+    } else if text.starts_with("*   Trying")
+        || text.starts_with("* Connected to")
+        || text.starts_with("* Failed")
+        || text.starts_with("* Could not")
+        || text.starts_with("* SSL certificate")
+    {
+        TIER_CONNECTION
+    } else if text.starts_with('*')
+        || text.starts_with("{ [")
+        || text.starts_with("} [")
+        || text.trim() == ">"
+        || text.trim() == "<"
+    {
+        // tls handshake, alpn, transfer bookkeeping
+        TIER_CHATTER
+    } else {
+        TIER_BODY
+    }
+}
+
+pub struct Curl {
+    confidence: Confidence,
+}
+impl Curl {
+    pub fn new(sample_lines: &Lines) -> Curl {
+        let has_request = sample_lines.lines.iter().any(|l| is_request_line(&l.text));
+        let has_verbose = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.starts_with("* ") || is_status_line(&l.text));
+        Curl {
+            confidence: if has_request && has_verbose {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Curl {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tier(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const CURL: &str = "*   Trying 93.184.216.34:443...
+* TLSv1.3 (OUT), TLS handshake, Client hello (1):
+> GET /old HTTP/1.1
+> User-Agent: curl/8.5.0
+>
+< HTTP/1.1 301 Moved Permanently
+< Location: https://example.com/new
+< Date: Fri, 16 Oct 2026 10:00:00 GMT
+<
+<html>moved</html>
+";
+
+    #[gtest]
+    fn curl_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(CURL), 100, 20)?;
+        let p = Curl::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_CONNECTION,
+                TIER_CHATTER,
+                TIER_STATUS,
+                TIER_OTHER_HEADER,
+                TIER_CHATTER,
+                TIER_STATUS,
+                TIER_HEADER,
+                TIER_OTHER_HEADER,
+                TIER_CHATTER,
+                TIER_BODY,
+            ])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Header(Severity),
+    Span,
+    Body,
+    Other,
+}
+
+// rustc puts the severity first (`error[E0583]: ...`), gcc and clang put
+// the location first (`main.c:3:5: error: ...`)
+fn header_severity(text: &str) -> Option<Severity> {
+    let starts_with = |word: &str| {
+        text.strip_prefix(word)
+            .is_some_and(|rest| rest.starts_with(':') || rest.starts_with('['))
+    };
+    if starts_with("error") {
+        return Some(Severity::Error);
+    } else if starts_with("warning") {
+        return Some(Severity::Warning);
+    } else if starts_with("note") || starts_with("help") {
+        return Some(Severity::Note);
+    }
+    let (location, message) = text.split_once(": ")?;
+    if !location.contains(|c: char| c.is_ascii_digit()) || location.contains(' ') {
+        return None;
+    }
+    if message.starts_with("error:") || message.starts_with("fatal error:") {
+        Some(Severity::Error)
+    } else if message.starts_with("warning:") {
+        Some(Severity::Warning)
+    } else if message.starts_with("note:") {
+        Some(Severity::Note)
+    } else {
+        None
+    }
+}
+
+pub fn kind(text: &str) -> Kind {
+    let trimmed = text.trim_start();
+    if let Some(severity) = header_severity(text) {
+        Kind::Header(severity)
+    } else if trimmed.starts_with("--> ") {
+        Kind::Span
+    } else if trimmed.starts_with('|')
+        || trimmed.starts_with("= ")
+        || trimmed.starts_with("::: ")
+        || trimmed.starts_with("...")
+        || trimmed
+            .split_once(" |")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    {
+        Kind::Body
+    } else {
+        Kind::Other
+    }
+}
+
+fn tier(kind: Kind, severity: Severity) -> u32 {
+    match (kind, severity) {
+        (Kind::Header(Severity::Error), _) | (Kind::Span, Severity::Error) => 0,
+        (Kind::Header(Severity::Warning), _) | (Kind::Span, Severity::Warning) => 1,
+        (Kind::Other, _) => 2,
+        (Kind::Header(Severity::Note), _) | (Kind::Span, Severity::Note) => 3,
+        (Kind::Body, Severity::Error) => 4,
+        (Kind::Body, _) => 5,
+    }
+}
+
+pub struct CompilerDiagnostic {
+    confidence: Confidence,
+}
+impl CompilerDiagnostic {
+    pub fn new(sample_lines: &Lines) -> CompilerDiagnostic {
+        let kinds: Vec<Kind> = sample_lines.lines.iter().map(|l| kind(&l.text)).collect();
+        let has_header = kinds
+            .iter()
+            .any(|k| matches!(k, Kind::Header(Severity::Error | Severity::Warning)));
+        let has_body = kinds.iter().any(|k| matches!(k, Kind::Span | Kind::Body));
+        CompilerDiagnostic {
+            confidence: if has_header && has_body {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for CompilerDiagnostic {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut severity = Severity::Note;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let kind = kind(&line.text);
+            if let Kind::Header(s) = kind {
+                severity = s;
+            }
+            line.prio.push(tier(kind, severity));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lines::LineStatus;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const RUSTC: &str = "   Compiling oneless v0.1.0
+warning: unused import: `Error`
+ --> src/lines.rs:1:14
+  |
+1 | use anyhow::{Error, Result};
+  |              ^^^^^
+  |
+  = note: `#[warn(unused_imports)]` on by default
+error[E0583]: file not found for module `cat`
+ --> src/main.rs:6:1
+  |
+6 | mod cat;
+  | ^^^^^^^^
+  |
+  = help: to create the module `cat`, create file \"src/cat.rs\"
+error: could not compile `oneless` due to 1 previous error
+";
+
+    #[gtest]
+    fn diagnostic_kinds() {
+        expect_that!(kind("error[E0583]: x"), eq(Kind::Header(Severity::Error)));
+        expect_that!(
+            kind("main.c:3:5: error: x"),
+            eq(Kind::Header(Severity::Error))
+        );
+        expect_that!(
+            kind("main.c:3:5: warning: x"),
+            eq(Kind::Header(Severity::Warning))
+        );
+        expect_that!(
+            kind("main.c:2:1: note: x"),
+            eq(Kind::Header(Severity::Note))
+        );
+        expect_that!(kind("    3 |   foo();"), eq(Kind::Body));
+        expect_that!(kind(" --> src/main.rs:6:1"), eq(Kind::Span));
+        expect_that!(kind("   Compiling x v0.1.0"), eq(Kind::Other));
+        expect_that!(kind("an error: in prose"), eq(Kind::Other));
+    }
+
+    #[gtest]
+    fn diagnostic_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(RUSTC), 80, 20)?;
+        let p = CompilerDiagnostic::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![1, 1]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![0, 8]));
+        expect_that!(&lines.lines[9].prio, eq(&vec![0, 9]));
+        expect_that!(&lines.lines[11].prio, eq(&vec![4, 11]));
+
+        lines.target_lines = 5;
+        lines.prune();
+        expect_that!(lines.lines[8].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[9].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[15].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[3].status, not(eq(&LineStatus::Kept)));
+        Ok(())
+    }
+}
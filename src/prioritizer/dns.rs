@@ -0,0 +1,163 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_ANSWER: u32 = 0;
+const TIER_STATUS: u32 = 1;
+const TIER_QUESTION: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_NOISE: u32 = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    None,
+    Question,
+    Answer,
+    // authority, additional
+    Noise,
+}
+
+fn is_dig(text: &str) -> bool {
+    text.starts_with("; <<>> DiG") || text.starts_with(";; ->>HEADER<<-")
+}
+
+// `;; ANSWER SECTION:`
+fn section(text: &str) -> Option<Section> {
+    let name = text.strip_prefix(";; ")?.strip_suffix(" SECTION:")?;
+    Some(match name {
+        "QUESTION" => Section::Question,
+        "ANSWER" => Section::Answer,
+        _ => Section::Noise,
+    })
+}
+
+fn dig_tier(text: &str, section: Section) -> u32 {
+    if text.starts_with(";; ->>HEADER<<-") {
+        TIER_ANSWER
+    } else if text.starts_with(";; Query time") || text.starts_with(";; SERVER") {
+        TIER_STATUS
+    } else if text.trim().is_empty() || text.starts_with(';') && section != Section::Question {
+        TIER_OTHER
+    } else {
+        match section {
+            Section::Answer => TIER_ANSWER,
+            Section::Question => TIER_QUESTION,
+            Section::Noise => TIER_NOISE,
+            Section::None => TIER_OTHER,
+        }
+    }
+}
+
+pub struct Dns {
+    confidence: Confidence,
+}
+impl Dns {
+    pub fn new(sample_lines: &Lines) -> Dns {
+        let dig = sample_lines.lines.iter().any(|l| is_dig(&l.text));
+        let nslookup = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| l.text.starts_with("Server:"))
+            && sample_lines
+                .lines
+                .get(1)
+                .is_some_and(|l| l.text.starts_with("Address:"));
+        Dns {
+            confidence: if dig || nslookup {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Dns {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let dig = lines.lines.iter().any(|l| is_dig(&l.text));
+        let mut current_section = Section::None;
+        // nslookup prints the server it asked, a blank line, then the answers
+        let mut past_server = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = if dig {
+                if let Some(s) = section(&line.text) {
+                    current_section = s;
+                    if s == Section::Answer {
+                        TIER_ANSWER
+                    } else {
+                        TIER_OTHER
+                    }
+                } else {
+                    dig_tier(&line.text, current_section)
+                }
+            } else if line.text.starts_with("** ") {
+                TIER_ANSWER
+            } else if line.text.trim().is_empty() {
+                past_server = true;
+                TIER_OTHER
+            } else if past_server {
+                TIER_ANSWER
+            } else {
+                TIER_STATUS
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const DIG: &str = "; <<>> DiG 9.18.18 <<>> example.com
+;; ->>HEADER<<- opcode: QUERY, status: NOERROR, id: 1234
+;; QUESTION SECTION:
+;example.com.\t\t\tIN\tA
+;; ANSWER SECTION:
+example.com.\t\t3600\tIN\tA\t93.184.216.34
+;; AUTHORITY SECTION:
+example.com.\t\t3600\tIN\tNS\ta.iana-servers.net.
+;; Query time: 12 msec
+";
+
+    #[gtest]
+    fn dns_dig() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DIG), 100, 20)?;
+        let p = Dns::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_OTHER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ANSWER, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_OTHER, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_QUESTION, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ANSWER, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ANSWER, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_OTHER, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_NOISE, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_STATUS, 8]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn dns_nslookup() -> Result<()> {
+        let c = Cursor::new(
+            "Server:\t\t1.1.1.1\nAddress:\t1.1.1.1#53\n\nNon-authoritative answer:\nName:\texample.com\nAddress: 93.184.216.34\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Dns::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_STATUS, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_OTHER, 2]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ANSWER, 5]));
+        Ok(())
+    }
+}
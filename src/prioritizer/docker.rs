@@ -0,0 +1,145 @@
+use super::table::{field, named_columns};
+use super::units::parse_size;
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_PROBLEM: u32 = 1;
+const TIER_STOPPED: u32 = 2;
+const TIER_HEALTHY: u32 = 3;
+// `docker images` has no states, apart from dangling images
+const TIER_IMAGE: u32 = 2;
+
+fn is_ps_header(text: &str) -> bool {
+    text.starts_with("CONTAINER ID") && text.contains("STATUS")
+}
+
+fn is_images_header(text: &str) -> bool {
+    text.starts_with("REPOSITORY") && text.contains("IMAGE ID")
+}
+
+// `Up 2 hours (unhealthy)`, `Exited (137) 5 minutes ago`, `Restarting (1) ...`
+fn container_tier(status: &str) -> u32 {
+    let exit_code = status
+        .strip_prefix("Exited (")
+        .and_then(|s| s.split_once(')'))
+        .map(|(code, _)| code);
+    if status.starts_with("Restarting")
+        || status.starts_with("Dead")
+        || status.contains("(unhealthy)")
+        || exit_code.is_some_and(|c| c != "0")
+    {
+        TIER_PROBLEM
+    } else if status.starts_with("Up") {
+        TIER_HEALTHY
+    } else {
+        TIER_STOPPED
+    }
+}
+
+pub struct Docker {
+    confidence: Confidence,
+}
+impl Docker {
+    pub fn new(sample_lines: &Lines) -> Docker {
+        Docker {
+            confidence: match sample_lines.lines.first() {
+                Some(l) if is_ps_header(&l.text) || is_images_header(&l.text) => {
+                    Confidence::Certain
+                }
+                _ => Confidence::Low,
+            },
+        }
+    }
+}
+impl Prioritizer for Docker {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let Some(header) = lines.lines.first().map(|l| l.text.clone()) else {
+            return Ok(());
+        };
+        let columns = named_columns(&header);
+        let sizes: Vec<f64> = lines
+            .lines
+            .iter()
+            .map(|l| field(&l.text, &columns, "SIZE").and_then(|s| parse_size(&s)))
+            .map(|s| s.unwrap_or(0.0))
+            .collect();
+        let mut sorted: Vec<f64> = sizes.clone();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            if line_number == 0 {
+                line.prio.push(TIER_HEADER);
+                line.prio.push(0);
+            } else if is_ps_header(&header) {
+                let status = field(&line.text, &columns, "STATUS").unwrap_or_default();
+                line.prio.push(container_tier(&status));
+                line.prio.push(line_number as u32);
+            } else {
+                // dangling images first, then the biggest
+                let repository = field(&line.text, &columns, "REPOSITORY");
+                let rank = sorted.partition_point(|s| *s > sizes[line_number]);
+                line.prio.push(if repository.as_deref() == Some("<none>") {
+                    TIER_PROBLEM
+                } else {
+                    TIER_IMAGE
+                });
+                line.prio.push(rank as u32);
+            }
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const DOCKER_PS: &str =
+        "CONTAINER ID   IMAGE     COMMAND                  STATUS                       NAMES
+0123456789ab   nginx     \"/docker-entrypoint.…\"   Up 2 hours                   web
+123456789abc   redis     \"docker-entrypoint.s…\"   Restarting (1) 3 seconds ago cache
+23456789abcd   postgres  \"docker-entrypoint.s…\"   Exited (0) 5 days ago        db
+3456789abcde   app       \"./run\"                  Up 1 hour (unhealthy)        app
+";
+
+    const DOCKER_IMAGES: &str = "REPOSITORY   TAG       IMAGE ID       CREATED        SIZE
+nginx        latest    605c77e624dd   2 weeks ago    141MB
+<none>       <none>    1b2c3d4e5f60   3 weeks ago    1.2GB
+alpine       3.19      05455a08881e   2 months ago   7.38MB
+";
+
+    #[gtest]
+    fn docker_ps() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DOCKER_PS), 100, 20)?;
+        let p = Docker::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_HEALTHY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PROBLEM, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_STOPPED, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_PROBLEM, 4]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn docker_images() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DOCKER_IMAGES), 100, 20)?;
+        let p = Docker::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_IMAGE, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PROBLEM, 0]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_IMAGE, 2]));
+        Ok(())
+    }
+}
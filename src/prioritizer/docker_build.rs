@@ -0,0 +1,142 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+use std::collections::HashSet;
+
+const TIER_ERROR: u32 = 0;
+const TIER_STEP: u32 = 1;
+const TIER_CACHED_STEP: u32 = 2;
+const TIER_STATUS: u32 = 3;
+const TIER_OUTPUT: u32 = 4;
+
+// buildkit prefixes everything with the vertex number: `#6 [builder 3/6] RUN make`
+fn vertex(text: &str) -> Option<(&str, &str)> {
+    let (number, rest) = text.strip_prefix('#')?.split_once(' ')?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some((number, rest))
+}
+
+// the legacy builder: `Step 3/7 : RUN make`
+fn is_legacy_step(text: &str) -> bool {
+    text.strip_prefix("Step ")
+        .and_then(|t| t.split_once(" : "))
+        .is_some_and(|(steps, _)| steps.contains('/'))
+}
+
+// the summary buildkit prints after a failed step, up to the end
+fn starts_error_summary(text: &str) -> bool {
+    text.starts_with("------") || text.starts_with("ERROR:") || text.starts_with("error:")
+}
+
+pub struct DockerBuild {
+    confidence: Confidence,
+}
+impl DockerBuild {
+    pub fn new(sample_lines: &Lines) -> DockerBuild {
+        let legacy = sample_lines.lines.iter().any(|l| is_legacy_step(&l.text))
+            && sample_lines
+                .lines
+                .iter()
+                .any(|l| l.text.starts_with(" ---> "));
+        DockerBuild {
+            confidence: if legacy
+                || (sample_lines.lines.len() > 1
+                    && share_of_lines(sample_lines, |t| vertex(t).is_some()) >= 0.5)
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for DockerBuild {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let cached: HashSet<String> = lines
+            .lines
+            .iter()
+            .filter_map(|l| vertex(&l.text))
+            .filter(|(_, rest)| *rest == "CACHED")
+            .map(|(number, _)| number.to_string())
+            .collect();
+        let len: usize = lines.lines.len();
+        let mut in_error_summary = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            in_error_summary = in_error_summary || starts_error_summary(&line.text);
+            let tier = match vertex(&line.text) {
+                _ if in_error_summary => TIER_ERROR,
+                Some((number, rest)) if rest.starts_with('[') => {
+                    if cached.contains(number) {
+                        TIER_CACHED_STEP
+                    } else {
+                        TIER_STEP
+                    }
+                }
+                Some((_, rest)) if rest.starts_with("ERROR") => TIER_ERROR,
+                Some((_, rest)) if rest == "CACHED" || rest.starts_with("DONE") => TIER_STATUS,
+                None if is_legacy_step(&line.text) => TIER_STEP,
+                None if line.text == " ---> Using cache" => TIER_STATUS,
+                _ if line.text.to_ascii_lowercase().contains("error") => TIER_ERROR,
+                _ => TIER_OUTPUT,
+            };
+            line.prio.push(tier);
+            // output closest to the end of the build explains how it ended
+            line.prio.push((len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const BUILDKIT: &str = "#5 [builder 2/3] RUN apt-get update
+#5 CACHED
+#6 [builder 3/3] RUN cargo build
+#6 0.532 Compiling foo v0.1.0
+#6 12.30 error[E0425]: cannot find value `x`
+#6 ERROR: process did not complete successfully: exit code: 101
+------
+ > [builder 3/3] RUN cargo build:
+";
+
+    #[gtest]
+    fn docker_build_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(BUILDKIT), 100, 20)?;
+        let p = DockerBuild::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_CACHED_STEP, 7]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_STATUS, 6]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_STEP, 5]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_OUTPUT, 4]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ERROR, 3]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ERROR, 2]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_ERROR, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn docker_build_legacy() -> Result<()> {
+        let c = Cursor::new(
+            "Step 1/2 : FROM alpine\n ---> 05455a08881e\nStep 2/2 : RUN make\n ---> Using cache\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = DockerBuild::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_STEP, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_STATUS, 0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use super::units::parse_size;
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_UNPARSED: u32 = 0;
+const TIER_ENTRY: u32 = 1;
+
+// `SIZE<tab>PATH`
+fn entry_size(text: &str) -> Option<f64> {
+    let (size, path) = text.split_once('\t')?;
+    if path.is_empty() {
+        return None;
+    }
+    parse_size(size.trim())
+}
+
+pub struct Du {
+    confidence: Confidence,
+}
+impl Du {
+    pub fn new(sample_lines: &Lines) -> Du {
+        Du {
+            confidence: if sample_lines.lines.len() > 1
+                && share_of_lines(sample_lines, |t| entry_size(t).is_some()) >= 0.9
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Du {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let sizes: Vec<Option<f64>> = lines.lines.iter().map(|l| entry_size(&l.text)).collect();
+        let mut sorted: Vec<f64> = sizes.iter().flatten().copied().collect();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            match sizes[line_number] {
+                // permission denied and such
+                None => {
+                    line.prio.push(TIER_UNPARSED);
+                    line.prio.push(0);
+                }
+                Some(size) => {
+                    // number of entries bigger than this one
+                    let rank = sorted.partition_point(|s| *s > size);
+                    line.prio.push(TIER_ENTRY);
+                    line.prio.push(rank as u32);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn du_prioritizer() -> Result<()> {
+        let c =
+            Cursor::new("4.0K\t./a/b/c/tiny\n1.4G\t./a/b/huge\n512M\t./a/b\ndu: cannot read ./x\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Du::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(10));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_ENTRY, 2]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ENTRY, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_ENTRY, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_UNPARSED, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn du_detection() {
+        let c = Cursor::new("4.0K\t./a\n1.4G\t./b\n512M\t.\n");
+        let lines = Lines::from_reader(c, 100, 20).unwrap();
+        expect_that!(u32::from(Du::new(&lines).confidence()), eq(100));
+    }
+}
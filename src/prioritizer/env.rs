@@ -0,0 +1,136 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_CREDENTIAL: u32 = 0;
+const TIER_INTERESTING: u32 = 1;
+const TIER_VARIABLE: u32 = 2;
+const TIER_OTHER: u32 = 3;
+
+const INTERESTING: [&str; 12] = [
+    "PATH",
+    "HOME",
+    "USER",
+    "SHELL",
+    "PWD",
+    "LANG",
+    "TERM",
+    "EDITOR",
+    "TZ",
+    "LD_LIBRARY_PATH",
+    "PYTHONPATH",
+    "KUBECONFIG",
+];
+const INTERESTING_AFFIXES: [&str; 3] = ["_PROXY", "LC_", "XDG_RUNTIME"];
+// names that suggest a secret ended up in the environment
+const CREDENTIAL_WORDS: [&str; 7] = [
+    "TOKEN",
+    "SECRET",
+    "PASSWORD",
+    "PASSWD",
+    "API_KEY",
+    "CREDENTIAL",
+    "PRIVATE_KEY",
+];
+
+// `KEY=value`, also `declare -x KEY="value"` and `export KEY=value`
+fn name(text: &str) -> Option<&str> {
+    let text = text
+        .strip_prefix("declare -x ")
+        .or_else(|| text.strip_prefix("export "))
+        .unwrap_or(text);
+    let (name, _) = text.split_once('=')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(name)
+}
+
+fn tier(name: &str) -> u32 {
+    let upper = name.to_ascii_uppercase();
+    if CREDENTIAL_WORDS.iter().any(|w| upper.contains(w)) {
+        TIER_CREDENTIAL
+    } else if INTERESTING.contains(&upper.as_str())
+        || INTERESTING_AFFIXES.iter().any(|a| upper.contains(a))
+    {
+        TIER_INTERESTING
+    } else {
+        TIER_VARIABLE
+    }
+}
+
+pub struct Env {
+    confidence: Confidence,
+}
+impl Env {
+    pub fn new(sample_lines: &Lines) -> Env {
+        Env {
+            confidence: if sample_lines.lines.len() > 2
+                && share_of_lines(sample_lines, |t| name(t).is_some()) >= 0.8
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Env {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // the rest is kept in alphabetical order of names, whatever order it came in
+        let mut names: Vec<&str> = lines.lines.iter().filter_map(|l| name(&l.text)).collect();
+        names.sort_unstable();
+        let ranks: Vec<u32> = lines
+            .lines
+            .iter()
+            .map(|l| match name(&l.text) {
+                Some(n) => names.partition_point(|m| *m < n) as u32,
+                None => u32::MAX,
+            })
+            .collect();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // multi-line values and `set`'s function bodies
+            let tier = name(&line.text).map_or(TIER_OTHER, tier);
+            line.prio.push(tier);
+            line.prio.push(ranks[line_number]);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const ENV: &str = "SHLVL=1
+PATH=/usr/local/bin:/usr/bin
+GITHUB_TOKEN=ghp_xxx
+https_proxy=http://proxy:3128
+COLORTERM=truecolor
+";
+
+    #[gtest]
+    fn env_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(ENV), 100, 20)?;
+        let p = Env::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_VARIABLE, 3, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_INTERESTING, 2, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_CREDENTIAL, 1, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_INTERESTING, 4, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_VARIABLE, 0, 4]));
+        Ok(())
+    }
+}
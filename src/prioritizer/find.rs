@@ -0,0 +1,117 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+use std::collections::HashSet;
+
+// rarely what anyone is looking for in a listing
+const VENDORED: [&str; 9] = [
+    "node_modules",
+    "target",
+    "vendor",
+    "__pycache__",
+    "build",
+    "dist",
+    ".venv",
+    "venv",
+    "site-packages",
+];
+
+// `./src/main.rs` or `/etc/hosts`, with no `:` so grep output does not qualify
+fn is_path(text: &str) -> bool {
+    (text == "." || text.starts_with("./") || text.starts_with('/')) && !text.contains(':')
+}
+
+fn components(text: &str) -> Vec<&str> {
+    text.trim_start_matches("./")
+        .split('/')
+        .filter(|c| !c.is_empty() && *c != ".")
+        .collect()
+}
+
+fn is_hidden_or_vendored(components: &[&str]) -> bool {
+    components
+        .iter()
+        .any(|c| (c.starts_with('.') && *c != "..") || VENDORED.contains(c))
+}
+
+pub struct Find {
+    confidence: Confidence,
+}
+impl Find {
+    pub fn new(sample_lines: &Lines) -> Find {
+        Find {
+            confidence: if sample_lines.lines.len() > 2
+                && share_of_lines(sample_lines, is_path) >= 0.9
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Find {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // find lists a directory before its contents, so the first path under
+        // each top-level directory is also its shallowest
+        let mut seen_top_levels: HashSet<String> = HashSet::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let components = components(&line.text);
+            let top_level = components.first().copied().unwrap_or("");
+            let representative = seen_top_levels.insert(top_level.to_string());
+            line.prio.push(if representative { 0 } else { 1 });
+            line.prio.push(is_hidden_or_vendored(&components) as u32);
+            line.prio.push(components.len() as u32);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const FIND: &str = ".
+./src
+./src/main.rs
+./src/prioritizer/find.rs
+./.git
+./.git/HEAD
+./node_modules/left-pad/index.js
+./README.md
+";
+
+    #[gtest]
+    fn find_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(FIND), 100, 20)?;
+        let p = Find::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0, 0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 0, 1, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![1, 0, 2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![1, 0, 3, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![0, 1, 1, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![1, 1, 2, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![0, 1, 3, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![0, 0, 1, 7]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn find_not_grep_output() -> Result<()> {
+        let c = Cursor::new("./a.rs:1:x\n./b.rs:2:y\n./c.rs:3:z\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Find::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
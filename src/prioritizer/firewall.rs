@@ -0,0 +1,149 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_CHAIN: u32 = 0;
+const TIER_BLOCKING: u32 = 1;
+const TIER_HIT: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_UNUSED: u32 = 4;
+
+// iptables targets, nft verdicts
+const BLOCKING_TARGETS: [&str; 6] = ["REJECT", "DROP", "reject", "reject;", "drop", "drop;"];
+
+// `Chain INPUT (policy DROP 12 packets, 720 bytes)` from `iptables -L -v`,
+// `-P INPUT DROP` from `iptables -S`, `chain input {` and `type filter hook
+// input priority 0; policy drop;` from `nft list ruleset`
+fn is_chain(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("Chain ")
+        || trimmed.starts_with("-P ")
+        || trimmed.starts_with("-N ")
+        || trimmed.starts_with("chain ")
+        || trimmed.starts_with("table ")
+        || trimmed.contains("policy ")
+        || trimmed.starts_with("pkts ")
+}
+
+fn is_blocking(text: &str) -> bool {
+    text.split_whitespace()
+        .any(|w| BLOCKING_TARGETS.contains(&w))
+}
+
+// the packet counter leads `iptables -L -v` rules, nft puts `counter packets N`
+// anywhere in the rule
+fn packets(text: &str) -> Option<u64> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if let Some(i) = words.iter().position(|w| *w == "packets") {
+        return words.get(i + 1)?.parse().ok();
+    }
+    let first = words.first()?;
+    let end = first
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(first.len());
+    let (number, suffix) = first.split_at(end);
+    let number: u64 = number.parse().ok()?;
+    // `iptables -v` abbreviates large counters: `12K`, `3M`
+    matches!(suffix, "" | "K" | "M" | "G").then_some(number)
+}
+
+pub struct Firewall {
+    confidence: Confidence,
+}
+impl Firewall {
+    pub fn new(sample_lines: &Lines) -> Firewall {
+        let iptables = sample_lines.lines.iter().any(|l| {
+            l.text.starts_with("Chain ") && l.text.contains('(') || l.text.starts_with("-P ")
+        });
+        let nft = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.starts_with("table ") && l.text.trim_end().ends_with('{'));
+        Firewall {
+            confidence: if iptables || nft {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Firewall {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = if is_chain(&line.text) || line.text.trim() == "}" {
+                TIER_CHAIN
+            } else if is_blocking(&line.text) {
+                TIER_BLOCKING
+            } else {
+                match packets(&line.text) {
+                    Some(0) => TIER_UNUSED,
+                    Some(_) => TIER_HIT,
+                    None => TIER_OTHER,
+                }
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const IPTABLES: &str = "Chain INPUT (policy ACCEPT 0 packets, 0 bytes)
+ pkts bytes target     prot opt in     out     source               destination
+  12K  720K ACCEPT     all  --  lo     any     anywhere             anywhere
+    0     0 ACCEPT     tcp  --  any    any     anywhere             anywhere             tcp dpt:8080
+    3   180 DROP       all  --  any    any     10.0.0.0/8           anywhere
+";
+
+    #[gtest]
+    fn firewall_iptables() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(IPTABLES), 200, 20)?;
+        let p = Firewall::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_CHAIN, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_CHAIN, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_HIT, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_UNUSED, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_BLOCKING, 4]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn firewall_nft() -> Result<()> {
+        let c = Cursor::new(
+            "table inet filter {\n\tchain input {\n\t\ttype filter hook input priority 0; policy drop;\n\t\ttcp dport 22 counter packets 42 bytes 2520 accept\n\t\ttcp dport 80 counter packets 0 bytes 0 accept\n\t}\n}\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Firewall::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_CHAIN,
+                TIER_CHAIN,
+                TIER_CHAIN,
+                TIER_HIT,
+                TIER_UNUSED,
+                TIER_CHAIN,
+                TIER_CHAIN,
+            ])
+        );
+        Ok(())
+    }
+}
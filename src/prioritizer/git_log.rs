@@ -0,0 +1,155 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+// header lines (commit, author, date, subject) beat body lines, then recent
+// commits (the first ones in `git log` output) beat older ones
+const TIER_COMMIT: u32 = 0;
+const TIER_SUBJECT: u32 = 1;
+const TIER_HEADER: u32 = 2;
+const TIER_BODY: u32 = 3;
+
+fn is_hash(s: &str) -> bool {
+    (7..=40).contains(&s.len())
+        && s.chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())
+        && s.chars().any(|c| c.is_ascii_digit())
+}
+
+// strips the `--graph` decoration
+fn strip_graph(s: &str) -> &str {
+    s.trim_start_matches(['*', '|', '/', '\\', ' '])
+}
+
+fn is_commit_line(s: &str) -> bool {
+    match strip_graph(s).strip_prefix("commit ") {
+        Some(rest) => is_hash(rest.split(' ').next().unwrap_or("")),
+        None => false,
+    }
+}
+
+fn is_oneline(s: &str) -> bool {
+    is_hash(strip_graph(s).split(' ').next().unwrap_or(""))
+}
+
+pub struct GitLog {
+    confidence: Confidence,
+}
+impl GitLog {
+    pub fn new(sample_lines: &Lines) -> GitLog {
+        let starts_with_commit = sample_lines
+            .lines
+            .iter()
+            .find(|l| !l.text.trim().is_empty())
+            .is_some_and(|l| is_commit_line(&l.text));
+        let has_author = sample_lines
+            .lines
+            .iter()
+            .any(|l| strip_graph(&l.text).starts_with("Author: "));
+        let confidence = if starts_with_commit && has_author {
+            Confidence::Certain
+        } else if sample_lines.lines.len() > 2 && share_of_lines(sample_lines, is_oneline) > 0.9 {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        GitLog { confidence }
+    }
+}
+impl Prioritizer for GitLog {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut commit_number: u32 = 0;
+        let mut in_header = false;
+        let mut seen_commit_line = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = strip_graph(&line.text);
+            let tier = if is_commit_line(&line.text) {
+                if line_number > 0 {
+                    commit_number += 1;
+                }
+                in_header = true;
+                seen_commit_line = true;
+                TIER_COMMIT
+            } else if in_header && !text.is_empty() && line.text.starts_with(char::is_whitespace) {
+                in_header = false;
+                TIER_SUBJECT
+            } else if in_header {
+                TIER_HEADER
+            } else if !seen_commit_line && is_oneline(&line.text) {
+                if line_number > 0 {
+                    commit_number += 1;
+                }
+                TIER_COMMIT
+            } else {
+                TIER_BODY
+            };
+            line.prio.push((tier == TIER_BODY) as u32);
+            line.prio.push(commit_number);
+            line.prio.push(tier);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lines::LineStatus;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const GIT_LOG: &str = "commit 1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e
+Author: Some One <one@example.com>
+Date:   Mon Jun 2 10:00:00 2025 +0200
+
+    add the thing
+
+    the thing was missing
+
+commit 0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d
+Author: Some One <one@example.com>
+Date:   Sun Jun 1 10:00:00 2025 +0200
+
+    initial commit
+";
+
+    #[gtest]
+    fn git_log_detection() {
+        let lines = Lines::from_reader(Cursor::new(GIT_LOG), 80, 20).unwrap();
+        expect_that!(u32::from(GitLog::new(&lines).confidence()), eq(100));
+
+        let oneline = Cursor::new("1b2c3d4 add the thing\n0a1b2c3 initial commit\n9f8e7d6 x\n");
+        let lines = Lines::from_reader(oneline, 80, 20).unwrap();
+        expect_that!(u32::from(GitLog::new(&lines).confidence()), eq(30));
+
+        let prose = Cursor::new("commit to the plan\nAuthor: me\nsomething\n");
+        let lines = Lines::from_reader(prose, 80, 20).unwrap();
+        expect_that!(u32::from(GitLog::new(&lines).confidence()), eq(10));
+    }
+
+    #[gtest]
+    fn git_log_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(GIT_LOG), 80, 20)?;
+        let p = GitLog::new(&lines);
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0, TIER_COMMIT]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 0, TIER_HEADER]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![0, 0, TIER_SUBJECT]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![1, 0, TIER_BODY]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![0, 1, TIER_COMMIT]));
+        expect_that!(&lines.lines[12].prio, eq(&vec![0, 1, TIER_SUBJECT]));
+
+        lines.target_lines = 10;
+        lines.prune();
+        expect_that!(lines.lines[0].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[4].status, eq(&LineStatus::Kept));
+        expect_that!(lines.lines[6].status, not(eq(&LineStatus::Kept)));
+        expect_that!(lines.lines[12].status, eq(&LineStatus::Kept));
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_PANIC: u32 = 0;
+const TIER_PANICKING_FRAME: u32 = 1;
+const TIER_GOROUTINE: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_FRAME: u32 = 4;
+
+// `goroutine 1 [running]:`
+fn is_goroutine_header(text: &str) -> bool {
+    text.starts_with("goroutine ") && text.trim_end().ends_with("]:")
+}
+
+fn is_panic(text: &str) -> bool {
+    text.starts_with("panic: ")
+        || text.starts_with("fatal error: ")
+        || text.starts_with("[signal ")
+        || text.starts_with("SIGQUIT")
+}
+
+// each frame is a function line followed by an indented `file.go:line` line
+fn is_frame_location(text: &str) -> bool {
+    text.starts_with(char::is_whitespace) && text.contains(".go:")
+}
+
+pub struct GoPanic {
+    confidence: Confidence,
+}
+impl GoPanic {
+    pub fn new(sample_lines: &Lines) -> GoPanic {
+        let n_goroutines = sample_lines
+            .lines
+            .iter()
+            .filter(|l| is_goroutine_header(&l.text))
+            .count();
+        let has_panic = sample_lines.lines.iter().any(|l| is_panic(&l.text));
+        GoPanic {
+            confidence: if n_goroutines > 0 && (has_panic || n_goroutines > 1) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for GoPanic {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let has_panic = lines.lines.iter().any(|l| is_panic(&l.text));
+        // the goroutine that panicked is the first one reported after the
+        // panic, in a plain dump (SIGQUIT, debug.Stack) it's the running one
+        let mut panic_seen = false;
+        let mut panicking_found = false;
+        let mut in_panicking = false;
+        let mut depth: u32 = 0;
+        for line in lines.lines.iter_mut() {
+            let text = &line.text;
+            let tier = if is_panic(text) {
+                panic_seen = true;
+                TIER_PANIC
+            } else if is_goroutine_header(text) {
+                depth = 0;
+                in_panicking = !panicking_found
+                    && (if has_panic {
+                        panic_seen
+                    } else {
+                        text.contains("[running]")
+                    });
+                panicking_found |= in_panicking;
+                if in_panicking {
+                    TIER_PANIC
+                } else {
+                    TIER_GOROUTINE
+                }
+            } else if text.trim().is_empty() || text.starts_with("exit status") {
+                in_panicking = false;
+                TIER_OTHER
+            } else {
+                if !is_frame_location(text) {
+                    depth += 1;
+                }
+                if in_panicking {
+                    TIER_PANICKING_FRAME
+                } else if depth > 0 {
+                    TIER_FRAME
+                } else {
+                    TIER_OTHER
+                }
+            };
+            line.prio.push(tier);
+            line.prio.push(depth);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PANIC: &str = "panic: runtime error: index out of range [5] with length 3
+
+goroutine 1 [running]:
+main.foo(...)
+\t/home/u/main.go:10 +0x1d
+main.main()
+\t/home/u/main.go:5 +0x25
+
+goroutine 17 [chan receive]:
+net/http.(*Server).Serve(...)
+\t/usr/lib/go/src/net/http/server.go:3000 +0x3c
+";
+
+    #[gtest]
+    fn go_panic_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PANIC), 100, 20)?;
+        let p = GoPanic::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_PANIC, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PANIC, 0]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_PANICKING_FRAME, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_PANICKING_FRAME, 1]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_PANICKING_FRAME, 2]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_GOROUTINE, 0]));
+        expect_that!(&lines.lines[9].prio, eq(&vec![TIER_FRAME, 1]));
+        expect_that!(&lines.lines[10].prio, eq(&vec![TIER_FRAME, 1]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn go_dump_without_panic() -> Result<()> {
+        let c = Cursor::new("goroutine 3 [select]:\nmain.a()\ngoroutine 4 [running]:\nmain.b()\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = GoPanic::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_GOROUTINE, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FRAME, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PANIC, 0]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_PANICKING_FRAME, 1]));
+        Ok(())
+    }
+}
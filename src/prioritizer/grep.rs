@@ -0,0 +1,124 @@
+use super::diagnostic::{Kind, kind};
+use super::timestamp::leading_timestamp;
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+use std::collections::HashMap;
+
+// `src/main.rs:12:fn main() {` from `grep -rn`, or `src/main.rs:fn main() {`
+// from `grep -r`; context lines from `-C` use `-` instead of `:`. dates and
+// times, as in `2025-06-01T10:00:00Z`, are not paths
+fn path(text: &str) -> Option<&str> {
+    if matches!(kind(text), Kind::Header(_)) || leading_timestamp(text).is_some() {
+        return None;
+    }
+    for separator in [':', '-'] {
+        let mut parts = text.splitn(3, separator);
+        let (Some(path), Some(number), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        if !path.is_empty()
+            && !path.contains(' ')
+            && !path
+                .chars()
+                .all(|c| c.is_ascii_digit() || "-T:".contains(c))
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+        {
+            return Some(path);
+        }
+    }
+    let (path, _) = text.split_once(':')?;
+    (path.contains('/') && !path.contains(' ')).then_some(path)
+}
+
+pub struct Grep {
+    confidence: Confidence,
+}
+impl Grep {
+    pub fn new(sample_lines: &Lines) -> Grep {
+        Grep {
+            confidence: if sample_lines.lines.len() > 2
+                && share_of_lines(sample_lines, |t| path(t).is_some() || t == "--") >= 0.8
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Grep {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // the first hit in every file comes before the second hit in any file
+        let mut hits_per_file: HashMap<String, u32> = HashMap::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let index = match path(&line.text) {
+                Some(path) => {
+                    let hits = hits_per_file.entry(path.to_string()).or_insert(0);
+                    *hits += 1;
+                    *hits - 1
+                }
+                None => 1,
+            };
+            line.prio.push(index);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const GREP: &str = "src/main.rs:12:fn main() {
+src/main.rs:40:    main_loop();
+src/lines.rs:7:pub fn main_like() {}
+--
+src/options.rs-3-use clap::Parser;
+src/options.rs:4:// main options
+";
+
+    #[gtest]
+    fn grep_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(GREP), 100, 20)?;
+        let p = Grep::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![1, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![0, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![1, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![0, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![1, 5]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn grep_not_compiler_output() -> Result<()> {
+        let c = Cursor::new("main.c:3:5: error: x\nmain.c:4:5: warning: y\nmain.c:5:5: error: z\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Grep::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+
+    #[gtest]
+    fn grep_not_timestamped_log() -> Result<()> {
+        let c = Cursor::new(
+            "2025-06-01T10:00:00Z DEBUG polling\n2025-06-01T10:00:01Z WARN slow reply\n2025-06-01 10:00:02 ERROR no reply\n2025-06-01 10:00:03 DEBUG polling\n",
+        );
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Grep::new(&lines).confidence()), eq(10));
+        expect_that!(path("2025-06-01-12:x"), none());
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEALTH: u32 = 0;
+const TIER_PROBLEM: u32 = 1;
+const TIER_HEADER: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_READING: u32 = 4;
+
+// smart attributes where any non-zero raw value means the disk is going
+const CRITICAL_ATTRIBUTES: [u32; 6] = [5, 187, 188, 196, 197, 198];
+
+fn is_health(text: &str) -> bool {
+    text.starts_with("SMART overall-health") || text.starts_with("SMART Health Status")
+}
+
+fn is_header(text: &str) -> bool {
+    text.starts_with("smartctl ")
+        || text.starts_with("=== ")
+        || text.starts_with("ID# ")
+        || text.starts_with("Device Model")
+        || text.starts_with("Model Number")
+        || text.starts_with("Serial Number")
+        || text.starts_with("Adapter: ")
+}
+
+// `  5 Reallocated_Sector_Ct   0x0033   100   100   010    Pre-fail  Always       -       0`
+fn attribute_problem(text: &str) -> Option<bool> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let id: u32 = fields.first()?.parse().ok()?;
+    if fields.len() < 10 || !fields[2].starts_with("0x") {
+        return None;
+    }
+    let value: u32 = fields[3].parse().ok()?;
+    let threshold: u32 = fields[5].parse().ok()?;
+    let raw: u64 = fields[9].parse().unwrap_or(0);
+    Some(
+        fields[8] != "-"
+            || (threshold > 0 && value <= threshold)
+            || (CRITICAL_ATTRIBUTES.contains(&id) && raw > 0),
+    )
+}
+
+fn degrees(text: &str) -> Option<f64> {
+    let end = text.find("°C")?;
+    text[..end].trim_start_matches('+').parse().ok()
+}
+
+// `Package id 0:  +85.0°C  (high = +80.0°C, crit = +100.0°C)`
+fn sensor_problem(text: &str) -> Option<bool> {
+    let (_, reading) = text.split_once(':')?;
+    let reading = reading.trim_start();
+    if text.contains("ALARM") {
+        return Some(true);
+    }
+    let temperature = degrees(reading.split_whitespace().next()?)?;
+    let (_, limits) = reading.split_once('(').unwrap_or(("", ""));
+    let over = limits
+        .split(',')
+        .filter_map(|l| l.split_once('='))
+        .filter(|(name, _)| matches!(name.trim(), "high" | "crit"))
+        .filter_map(|(_, limit)| degrees(limit.trim()))
+        .any(|limit| limit > 0.0 && temperature >= limit);
+    Some(over)
+}
+
+fn tier(text: &str) -> u32 {
+    if is_health(text) {
+        TIER_HEALTH
+    } else if is_header(text) {
+        TIER_HEADER
+    } else {
+        match attribute_problem(text).or_else(|| sensor_problem(text)) {
+            Some(true) => TIER_PROBLEM,
+            Some(false) => TIER_READING,
+            None => TIER_OTHER,
+        }
+    }
+}
+
+pub struct HardwareHealth {
+    confidence: Confidence,
+}
+impl HardwareHealth {
+    pub fn new(sample_lines: &Lines) -> HardwareHealth {
+        let smartctl = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.starts_with("smartctl ") || is_health(&l.text));
+        let sensors = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.starts_with("Adapter: "));
+        HardwareHealth {
+            confidence: if smartctl || sensors {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for HardwareHealth {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let mut previous_blank = true;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // sensors names every chip on the line before its `Adapter:`
+            let tier = if previous_blank && !line.text.contains(':') && !line.text.is_empty() {
+                TIER_HEADER
+            } else {
+                tier(&line.text)
+            };
+            previous_blank = line.text.trim().is_empty();
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const SMARTCTL: &str = "smartctl 7.3 2022-02-28 r5338 [x86_64-linux-6.1.0] (local build)
+SMART overall-health self-assessment test result: PASSED
+ID# ATTRIBUTE_NAME          FLAG     VALUE WORST THRESH TYPE      UPDATED  WHEN_FAILED RAW_VALUE
+  5 Reallocated_Sector_Ct   0x0033   100   100   010    Pre-fail  Always       -       0
+  9 Power_On_Hours          0x0032   090   090   000    Old_age   Always       -       9000
+197 Current_Pending_Sector  0x0012   100   100   000    Old_age   Always       -       8
+  3 Spin_Up_Time            0x0027   005   005   021    Pre-fail  Always   FAILING_NOW 9000
+";
+
+    #[gtest]
+    fn hardware_health_smartctl() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(SMARTCTL), 200, 20)?;
+        let p = HardwareHealth::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_HEADER,
+                TIER_HEALTH,
+                TIER_HEADER,
+                TIER_READING,
+                TIER_READING,
+                TIER_PROBLEM,
+                TIER_PROBLEM,
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn hardware_health_sensors() -> Result<()> {
+        let c = Cursor::new(
+            "coretemp-isa-0000\nAdapter: ISA adapter\nPackage id 0:  +85.0°C  (high = +80.0°C, crit = +100.0°C)\nCore 0:        +45.0°C  (high = +80.0°C, crit = +100.0°C)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = HardwareHealth::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![TIER_HEADER, TIER_HEADER, TIER_PROBLEM, TIER_READING])
+        );
+        Ok(())
+    }
+}
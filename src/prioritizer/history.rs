@@ -0,0 +1,92 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+use std::collections::HashSet;
+
+// `  501  git status`, with a `*` after the number for entries edited in bash
+fn entry(text: &str) -> Option<(u32, &str)> {
+    let text = text.trim_start();
+    let end = text.find(|c: char| !c.is_ascii_digit())?;
+    let command = text[end..]
+        .strip_prefix("  ")
+        .or_else(|| text[end..].strip_prefix("* "))?;
+    Some((text[..end].parse().ok()?, command.trim()))
+}
+
+pub struct History {
+    confidence: Confidence,
+}
+impl History {
+    pub fn new(sample_lines: &Lines) -> History {
+        let numbers: Option<Vec<u32>> = sample_lines
+            .lines
+            .iter()
+            .map(|l| entry(&l.text).map(|(n, _)| n))
+            .collect();
+        // unlike `uniq -c` counts, history numbers only go up
+        let ascending = numbers.is_some_and(|n| n.len() > 2 && n.windows(2).all(|w| w[0] < w[1]));
+        History {
+            confidence: if ascending {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for History {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let len: usize = lines.lines.len();
+        let mut seen: HashSet<String> = HashSet::new();
+        // walking backwards, the first time a command is seen is its most recent run
+        for (line_number, line) in lines.lines.iter_mut().enumerate().rev() {
+            let command = entry(&line.text).map_or(line.text.as_str(), |(_, c)| c);
+            let most_recent = seen.insert(command.to_string());
+            line.prio.push(if most_recent { 0 } else { 1 });
+            line.prio.push((len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const HISTORY: &str = "  498  cargo test
+  499  git status
+  500  cargo test
+  501* vim src/main.rs
+  502  git status
+";
+
+    #[gtest]
+    fn history_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(HISTORY), 100, 20)?;
+        let p = History::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![1, 4]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![1, 3]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![0, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![0, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn history_not_counts() -> Result<()> {
+        let c = Cursor::new("  12  apple\n   3  banana\n  40  cherry\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(History::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
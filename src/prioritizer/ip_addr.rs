@@ -0,0 +1,141 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_ADDRESS: u32 = 1;
+const TIER_LINK: u32 = 2;
+const TIER_DETAIL: u32 = 3;
+
+// interfaces created by container runtimes and hypervisors, one per container
+const VIRTUAL_PREFIXES: [&str; 9] = [
+    "veth", "docker", "br-", "virbr", "cni", "flannel", "cali", "vnet", "tap",
+];
+
+// `2: eth0@if5: <BROADCAST,UP> mtu 1500 ... state UP`, ifconfig's
+// `eth0: flags=4163<UP,BROADCAST>  mtu 1500` and the older `eth0  Link encap:Ethernet`
+fn interface(text: &str) -> Option<&str> {
+    if text.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut words = text.split_whitespace();
+    let first = words.next()?;
+    if let Some(index) = first.strip_suffix(':')
+        && index.chars().all(|c| c.is_ascii_digit())
+    {
+        let name = words.next()?.trim_end_matches(':');
+        return Some(name.split('@').next().unwrap_or(name));
+    }
+    let second = words.next()?;
+    if let Some(name) = first.strip_suffix(':')
+        && second.starts_with("flags=")
+    {
+        return Some(name);
+    }
+    (second == "Link").then_some(first)
+}
+
+fn tier(text: &str) -> u32 {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with("inet ") || trimmed.starts_with("inet6 ") {
+        TIER_ADDRESS
+    } else if trimmed.starts_with("link/") || trimmed.starts_with("ether ") {
+        TIER_LINK
+    } else {
+        // lifetimes, RX/TX counters, altnames
+        TIER_DETAIL
+    }
+}
+
+pub struct IpAddr {
+    confidence: Confidence,
+}
+impl IpAddr {
+    pub fn new(sample_lines: &Lines) -> IpAddr {
+        let first_is_interface = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| interface(&l.text).is_some());
+        let has_address = sample_lines
+            .lines
+            .iter()
+            .any(|l| tier(&l.text) == TIER_ADDRESS);
+        IpAddr {
+            confidence: if first_is_interface && has_address {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for IpAddr {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // real interfaces are shown in full before any virtual one
+        let mut is_virtual = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match interface(&line.text) {
+                Some(name) => {
+                    is_virtual = VIRTUAL_PREFIXES.iter().any(|p| name.starts_with(p));
+                    TIER_HEADER
+                }
+                None => tier(&line.text),
+            };
+            line.prio.push(is_virtual as u32);
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const IP_ADDR: &str = "1: lo: <LOOPBACK,UP,LOWER_UP> mtu 65536 qdisc noqueue state UNKNOWN
+    link/loopback 00:00:00:00:00:00 brd 00:00:00:00:00:00
+    inet 127.0.0.1/8 scope host lo
+       valid_lft forever preferred_lft forever
+5: veth1a2b@if4: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 state UP
+    inet6 fe80::1/64 scope link
+";
+
+    #[gtest]
+    fn ip_addr_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(IP_ADDR), 100, 20)?;
+        let p = IpAddr::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, TIER_LINK, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![0, TIER_ADDRESS, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, TIER_DETAIL, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![1, TIER_HEADER, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![1, TIER_ADDRESS, 5]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn ip_addr_ifconfig() -> Result<()> {
+        let c = Cursor::new(
+            "eth0: flags=4163<UP,BROADCAST,RUNNING,MULTICAST>  mtu 1500\n        inet 10.0.0.5  netmask 255.255.255.0\n        RX packets 100  bytes 1000 (1.0 KB)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = IpAddr::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, TIER_ADDRESS, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![0, TIER_DETAIL, 2]));
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_WARNING: u32 = 1;
+const TIER_SUMMARY: u32 = 2;
+const TIER_STEP: u32 = 3;
+const TIER_TREE: u32 = 4;
+
+// npm, yarn classic, yarn berry and pnpm
+fn is_signature(text: &str) -> bool {
+    text.starts_with("npm WARN")
+        || text.starts_with("npm ERR!")
+        || text.starts_with("npm error")
+        || text.starts_with("yarn install v")
+        || text.starts_with("➤ YN")
+        || text.starts_with("Packages: +")
+        || text.starts_with("Progress: resolved")
+        || (text.starts_with("added ") && text.contains(" packages"))
+}
+
+fn is_error(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    text.starts_with("npm ERR!")
+        || lower.starts_with("npm error")
+        || lower.starts_with("error ")
+        || text.starts_with(" ERR_")
+        || text.contains('✕')
+        || text.starts_with("➤ YN0001")
+}
+
+fn is_warning(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    lower.starts_with("npm warn")
+        || lower.starts_with("warning ")
+        || text.trim_start().starts_with("WARN ")
+        || lower.contains("peer dep")
+        || lower.contains("unmet peer")
+        || lower.contains("deprecated")
+        || lower.contains("vulnerabilit")
+        // yarn berry's peer dependency warnings
+        || text.starts_with("➤ YN0002")
+        || text.starts_with("➤ YN0060")
+}
+
+fn is_summary(text: &str) -> bool {
+    let text = text.trim_start();
+    (text.starts_with("added ") && text.contains(" packages"))
+        || text.starts_with("removed ")
+        || text.starts_with("changed ")
+        || text.starts_with("audited ")
+        || text.starts_with("up to date")
+        || text.contains("packages are looking for funding")
+        || text.starts_with("success ")
+        || text.starts_with("Packages: ")
+        || text.contains("Done with warnings")
+        || text.contains("Done in ")
+}
+
+// `├── lodash@4.17.21`, `+ react 18.2.0`, `++++++` progress bars, `npm http fetch`
+fn is_tree(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with(['├', '└', '│', '+', '-'])
+        || trimmed.starts_with("npm http")
+        || trimmed.starts_with("Progress: ")
+        || trimmed.is_empty()
+}
+
+pub struct JsInstall {
+    confidence: Confidence,
+}
+impl JsInstall {
+    pub fn new(sample_lines: &Lines) -> JsInstall {
+        JsInstall {
+            confidence: if sample_lines.lines.iter().any(|l| is_signature(&l.text)) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for JsInstall {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            let tier = if is_error(text) {
+                TIER_ERROR
+            } else if is_warning(text) {
+                TIER_WARNING
+            } else if is_summary(text) {
+                TIER_SUMMARY
+            } else if is_tree(text) {
+                TIER_TREE
+            } else {
+                TIER_STEP
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const NPM: &str = "npm WARN deprecated inflight@1.0.6: This module is not supported
+npm http fetch GET 200 https://registry.npmjs.org/lodash 52ms
+
+added 1234 packages, and audited 1235 packages in 30s
+8 vulnerabilities (2 moderate, 6 high)
+To address all issues, run:
+npm ERR! code ERESOLVE
+";
+
+    #[gtest]
+    fn js_install_npm() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(NPM), 100, 20)?;
+        let p = JsInstall::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_WARNING, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_TREE, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_TREE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_SUMMARY, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_WARNING, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_STEP, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_ERROR, 6]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn js_install_yarn_and_pnpm() -> Result<()> {
+        let c = Cursor::new(
+            "yarn install v1.22.19\n[1/4] Resolving packages...\nwarning \" > react-dom@18.2.0\" has unmet peer dependency \"react@^18.2.0\".\nDone in 12.30s.\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        JsInstall::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_STEP, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_WARNING, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_SUMMARY, 3]));
+
+        let c = Cursor::new(
+            "Packages: +3\n+++\n└─┬ react-dom 18.2.0\n  └── ✕ unmet peer react@\"^18\": found 17.0.2\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = JsInstall::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_SUMMARY, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_TREE, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_TREE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ERROR, 3]));
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_FAILURE: u32 = 0;
+const TIER_FAILURE_DETAIL: u32 = 1;
+const TIER_OTHER: u32 = 2;
+const TIER_PASSED: u32 = 3;
+
+const PASS_MARKS: [&str; 3] = ["✓", "✔", "√"];
+const FAIL_MARKS: [&str; 4] = ["✕", "✗", "✖", "×"];
+
+fn is_passed(text: &str) -> bool {
+    let text = text.trim_start();
+    PASS_MARKS.iter().any(|m| text.starts_with(m))
+}
+
+fn is_failed(text: &str) -> bool {
+    let text = text.trim_start();
+    FAIL_MARKS.iter().any(|m| text.starts_with(m))
+        || text.starts_with("FAIL ")
+        // mocha numbers its failures: `  1) Suite name`
+        || text
+            .split_once(") ")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_summary(text: &str) -> bool {
+    let text = text.trim_start();
+    ["Tests:", "Test Suites:", "Snapshots:", "Time:"]
+        .iter()
+        .any(|s| text.starts_with(s))
+        || text.split_once(' ').is_some_and(|(n, rest)| {
+            n.chars().all(|c| c.is_ascii_digit())
+                && ["passing", "failing", "pending"]
+                    .iter()
+                    .any(|w| rest.starts_with(w))
+        })
+}
+
+// jest prints the details of each failed test below a `● Suite › test` header
+fn is_failure_header(text: &str) -> bool {
+    text.trim_start().starts_with("● ")
+}
+
+pub struct JsTest {
+    confidence: Confidence,
+}
+impl JsTest {
+    pub fn new(sample_lines: &Lines) -> JsTest {
+        let share = share_of_lines(sample_lines, |t| is_passed(t) || is_failed(t));
+        let has_summary = sample_lines.lines.iter().any(|l| is_summary(&l.text));
+        JsTest {
+            confidence: if has_summary && share >= 0.2 {
+                Confidence::Certain
+            } else if share >= 0.3 {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for JsTest {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut in_failure_detail = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            let tier = if is_failure_header(text) {
+                in_failure_detail = true;
+                TIER_FAILURE
+            } else if is_summary(text) {
+                in_failure_detail = false;
+                TIER_FAILURE
+            } else if is_failed(text) {
+                TIER_FAILURE
+            } else if is_passed(text) {
+                TIER_PASSED
+            } else if in_failure_detail {
+                TIER_FAILURE_DETAIL
+            } else {
+                TIER_OTHER
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const JEST: &str = "FAIL src/sum.test.js
+  sum
+    ✓ adds (3 ms)
+    ✕ subtracts (2 ms)
+    ✓ multiplies
+
+  ● sum › subtracts
+
+    expect(received).toBe(expected)
+
+Tests:       1 failed, 2 passed, 3 total
+";
+
+    #[gtest]
+    fn jest_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(JEST), 100, 20)?;
+        let p = JsTest::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_FAILURE, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_OTHER, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PASSED, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_FAILURE, 3]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_FAILURE, 6]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_FAILURE_DETAIL, 8]));
+        expect_that!(&lines.lines[10].prio, eq(&vec![TIER_FAILURE, 10]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn mocha_lines() {
+        expect_that!(is_passed("    ✔ works"), eq(true));
+        expect_that!(is_failed("    1) fails"), eq(true));
+        expect_that!(is_summary("  12 passing (40ms)"), eq(true));
+        expect_that!(is_summary("  1 failing"), eq(true));
+        expect_that!(is_summary("  12 apples"), eq(false));
+    }
+}
@@ -0,0 +1,147 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_SUMMARY: u32 = 1;
+const TIER_WARNING: u32 = 2;
+const TIER_BOUNDARY: u32 = 3;
+const TIER_INFO: u32 = 4;
+const TIER_DOWNLOAD: u32 = 5;
+
+fn is_gradle(text: &str) -> bool {
+    text.starts_with("> Task :")
+        || text.starts_with("BUILD SUCCESSFUL in ")
+        || text.starts_with("BUILD FAILED in ")
+}
+
+fn tier(text: &str) -> u32 {
+    // maven prefixes every line with its level
+    let (level, message) = match text.strip_prefix('[').and_then(|t| t.split_once("] ")) {
+        Some((level, message)) => (level, message),
+        None => ("", text),
+    };
+    // reactor summary lines: `module-a .......... SUCCESS [  1.2 s]`
+    let is_reactor_line =
+        message.contains(" ....") && (message.contains(" SUCCESS") || message.contains(" SKIPPED"));
+    let is_summary = message.starts_with("BUILD ")
+        || message.starts_with("Total time")
+        || message.starts_with("Reactor Summary")
+        || message.contains(" actionable task")
+        || is_reactor_line;
+    if level == "ERROR"
+        || message.starts_with("FAILURE")
+        || (message.starts_with("> Task :") && message.ends_with(" FAILED"))
+        || message.contains(" FAILURE [")
+        || message.contains(": error: ")
+    {
+        TIER_ERROR
+    } else if is_summary {
+        TIER_SUMMARY
+    } else if level == "WARNING" || level == "WARN" || message.contains(": warning: ") {
+        TIER_WARNING
+    } else if message.starts_with("Building ")
+        || message.starts_with("--- ")
+        || message.starts_with("> Task :")
+    {
+        TIER_BOUNDARY
+    } else if message.starts_with("Download")
+        || message.starts_with("Progress (")
+        || message.starts_with("------")
+    {
+        TIER_DOWNLOAD
+    } else {
+        TIER_INFO
+    }
+}
+
+pub struct JvmBuild {
+    confidence: Confidence,
+}
+impl JvmBuild {
+    pub fn new(sample_lines: &Lines) -> JvmBuild {
+        let maven = share_of_lines(sample_lines, |t| t.starts_with("[INFO]")) >= 0.3;
+        let gradle = sample_lines.lines.iter().any(|l| is_gradle(&l.text));
+        JvmBuild {
+            confidence: if maven || gradle {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for JvmBuild {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // gradle explains failures in `* What went wrong:` up to the next `* ` heading
+        let mut in_what_went_wrong = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            if line.text.starts_with("* ") {
+                in_what_went_wrong = line.text.starts_with("* What went wrong");
+            }
+            line.prio.push(if in_what_went_wrong {
+                TIER_ERROR
+            } else {
+                tier(&line.text)
+            });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const MAVEN: &str = "[INFO] Scanning for projects...
+[INFO] Building my-module 1.0-SNAPSHOT
+[INFO] --- maven-compiler-plugin:3.8.1:compile (default-compile) @ my-module ---
+Downloading from central: https://repo.maven.apache.org/maven2/junit/junit/4.13/junit-4.13.pom
+[WARNING] Using platform encoding (UTF-8 actually) to copy filtered resources
+[ERROR] /src/Foo.java:[12,5] cannot find symbol
+[INFO] BUILD FAILURE
+[INFO] module-a ........................................... SUCCESS [  1.2 s]
+";
+
+    #[gtest]
+    fn jvm_build_maven() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(MAVEN), 100, 20)?;
+        let p = JvmBuild::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_INFO, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_BOUNDARY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_BOUNDARY, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_DOWNLOAD, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_WARNING, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ERROR, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_SUMMARY, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_SUMMARY, 7]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn jvm_build_gradle() -> Result<()> {
+        let c = Cursor::new(
+            "> Task :app:compileJava FAILED\n* What went wrong:\nExecution failed for task ':app:compileJava'.\n* Try:\n> Run with --stacktrace option.\nBUILD FAILED in 3s\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = JvmBuild::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_ERROR, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_ERROR, 2]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_INFO, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_SUMMARY, 5]));
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_EXCEPTION: u32 = 0;
+const TIER_APPLICATION_FRAME: u32 = 1;
+const TIER_OTHER: u32 = 2;
+const TIER_ELIDED: u32 = 3;
+const TIER_FRAMEWORK_FRAME: u32 = 4;
+
+const FRAMEWORK_PACKAGES: [&str; 16] = [
+    "java.",
+    "javax.",
+    "jdk.",
+    "sun.",
+    "com.sun.",
+    "kotlin.",
+    "kotlinx.",
+    "scala.",
+    "groovy.",
+    "org.springframework.",
+    "org.apache.",
+    "org.junit.",
+    "org.hibernate.",
+    "org.gradle.",
+    "io.netty.",
+    "org.eclipse.",
+];
+
+const SOURCE_MARKERS: [&str; 6] = [
+    ".java:",
+    ".kt:",
+    ".scala:",
+    ".groovy:",
+    "(Native Method)",
+    "(Unknown Source)",
+];
+
+// `at com.foo.Bar.baz(Bar.java:42)`, the method, without the `at `
+fn frame(text: &str) -> Option<&str> {
+    let method = text.trim_start().strip_prefix("at ")?;
+    if SOURCE_MARKERS.iter().any(|m| method.contains(m)) {
+        Some(method)
+    } else {
+        None
+    }
+}
+
+fn is_framework(method: &str) -> bool {
+    // java 9+ prefixes the module, e.g. `java.base/java.lang.Thread.run`
+    let method = method.split_once('/').map_or(method, |(_, m)| m);
+    FRAMEWORK_PACKAGES.iter().any(|p| method.starts_with(p))
+}
+
+fn is_exception(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("Caused by:")
+        || text.starts_with("Suppressed:")
+        || text.starts_with("Exception in thread")
+        || text.split([':', ' ']).next().is_some_and(|class| {
+            class.contains('.') && (class.ends_with("Exception") || class.ends_with("Error"))
+        })
+}
+
+fn is_elided(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with("... ")
+        && (text.ends_with(" more") || text.ends_with(" common frames omitted"))
+}
+
+pub struct JvmStackTrace {
+    confidence: Confidence,
+}
+impl JvmStackTrace {
+    pub fn new(sample_lines: &Lines) -> JvmStackTrace {
+        let share = share_of_lines(sample_lines, |t| frame(t).is_some());
+        let has_exception = sample_lines.lines.iter().any(|l| is_exception(&l.text));
+        JvmStackTrace {
+            confidence: if has_exception && share >= 0.3 {
+                Confidence::Certain
+            } else if share >= 0.3 {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for JvmStackTrace {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // frames near the top of each trace (and each cause) matter most
+        let mut depth: u32 = 0;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = if let Some(method) = frame(&line.text) {
+                depth += 1;
+                if is_framework(method) {
+                    TIER_FRAMEWORK_FRAME
+                } else {
+                    TIER_APPLICATION_FRAME
+                }
+            } else if is_exception(&line.text) {
+                depth = 0;
+                TIER_EXCEPTION
+            } else if is_elided(&line.text) {
+                TIER_ELIDED
+            } else {
+                TIER_OTHER
+            };
+            line.prio.push(tier);
+            line.prio.push(depth);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TRACE: &str = "Exception in thread \"main\" java.lang.IllegalStateException: boom
+\tat com.example.app.Service.run(Service.java:42)
+\tat org.springframework.aop.Proxy.invoke(Proxy.java:10)
+\tat java.base/java.lang.Thread.run(Thread.java:833)
+Caused by: java.io.IOException: disk full
+\tat com.example.app.Disk.write(Disk.kt:12)
+\t... 3 more
+";
+
+    #[gtest]
+    fn jvm_stack_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TRACE), 100, 20)?;
+        let p = JvmStackTrace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_EXCEPTION, 0, 0]));
+        expect_that!(
+            &lines.lines[1].prio,
+            eq(&vec![TIER_APPLICATION_FRAME, 1, 1])
+        );
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FRAMEWORK_FRAME, 2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_FRAMEWORK_FRAME, 3, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_EXCEPTION, 0, 4]));
+        expect_that!(
+            &lines.lines[5].prio,
+            eq(&vec![TIER_APPLICATION_FRAME, 1, 5])
+        );
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_ELIDED, 1, 6]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn exception_lines() {
+        expect_that!(is_exception("java.lang.NullPointerException"), eq(true));
+        expect_that!(is_exception("Caused by: java.io.IOException: x"), eq(true));
+        expect_that!(is_exception("org.foo.BarError: x"), eq(true));
+        expect_that!(is_exception("an Exception happened"), eq(false));
+    }
+}
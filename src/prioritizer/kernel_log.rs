@@ -0,0 +1,144 @@
+use super::log_level::{SEVERITY_ERROR, SEVERITY_INFO, SEVERITY_WARN, severity};
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const FACILITIES: [&str; 9] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "local",
+];
+
+// `[    1.234567] ...`
+fn is_dmesg(text: &str) -> bool {
+    let Some((timestamp, _)) = text.strip_prefix('[').and_then(|t| t.split_once(']')) else {
+        return false;
+    };
+    let timestamp = timestamp.trim_start();
+    !timestamp.is_empty() && timestamp.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+// `dmesg -x`: `kern  :err   : [...]`
+fn is_dmesg_decoded(text: &str) -> bool {
+    text.split_once(':').is_some_and(|(facility, _)| {
+        // `local0` to `local7`
+        let facility = facility
+            .trim_end()
+            .trim_end_matches(|c: char| c.is_ascii_digit());
+        FACILITIES.contains(&facility)
+    })
+}
+
+fn is_journal_banner(text: &str) -> bool {
+    [
+        "-- Logs begin at",
+        "-- Journal begins at",
+        "-- No entries --",
+    ]
+    .iter()
+    .any(|b| text.starts_with(b))
+}
+
+// `dmesg --color` and `journalctl` paint errors red and warnings yellow
+fn color_severity(text: &str) -> Option<u32> {
+    let (_, codes) = text.split_once("\x1b[")?;
+    let (codes, _) = codes.split_once('m')?;
+    let codes: Vec<&str> = codes.split(';').collect();
+    if codes.iter().any(|c| *c == "31" || *c == "91") {
+        Some(SEVERITY_ERROR)
+    } else if codes.iter().any(|c| *c == "33" || *c == "93") || codes.ends_with(&["185"]) {
+        Some(SEVERITY_WARN)
+    } else {
+        None
+    }
+}
+
+pub struct KernelLog {
+    confidence: Confidence,
+}
+impl KernelLog {
+    pub fn new(sample_lines: &Lines) -> KernelLog {
+        let journal = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| is_journal_banner(&l.text));
+        KernelLog {
+            confidence: if journal
+                || (sample_lines.lines.len() > 1
+                    && share_of_lines(sample_lines, |t| is_dmesg(t) || is_dmesg_decoded(t)) >= 0.5)
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for KernelLog {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let len: usize = lines.lines.len();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // informational lines only survive if recent
+            let severity = color_severity(&line.text)
+                .or_else(|| severity(&line.text))
+                .unwrap_or(SEVERITY_INFO);
+            line.prio.push(if is_journal_banner(&line.text) {
+                SEVERITY_ERROR
+            } else {
+                severity
+            });
+            line.prio.push((len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::log_level::SEVERITY_DEBUG;
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const DMESG: &str = "[    0.000000] Linux version 6.8.0
+[    1.204511] usb 1-1: new high-speed USB device number 2
+[    2.318200] \x1b[33mACPI Warning: SystemIO range conflicts\x1b[0m
+[    3.100023] EXT4-fs (sda1): I/O error, dev sda, sector 2048
+[    4.000000] systemd[1]: Started Journal Service.
+";
+
+    #[gtest]
+    fn kernel_log_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DMESG), 100, 20)?;
+        let p = KernelLog::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![SEVERITY_INFO, 4]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![SEVERITY_INFO, 3]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![SEVERITY_WARN, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![SEVERITY_ERROR, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![SEVERITY_INFO, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn kernel_log_formats() -> Result<()> {
+        let c = Cursor::new("kern  :err   : [    3.1] ata1: failed\nkern  :debug : [    3.2] ok\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = KernelLog::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![SEVERITY_ERROR, 1]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![SEVERITY_DEBUG, 0]));
+
+        let c = Cursor::new(
+            "-- Journal begins at Mon 2026-10-12 --\nOct 16 10:00:00 host sshd[1]: ok\n",
+        );
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(KernelLog::new(&lines).confidence()), eq(100));
+        Ok(())
+    }
+}
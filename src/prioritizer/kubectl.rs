@@ -0,0 +1,130 @@
+use super::table::{field, named_columns};
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_CRASHING: u32 = 1;
+const TIER_UNHEALTHY: u32 = 2;
+const TIER_HEALTHY: u32 = 3;
+
+const HEALTHY_STATUSES: [&str; 6] = [
+    "Running",
+    "Completed",
+    "Succeeded",
+    "Ready",
+    "Active",
+    "Bound",
+];
+
+fn is_header(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    matches!(words.first(), Some(&"NAME" | &"NAMESPACE")) && words.contains(&"AGE")
+}
+
+// `READY 1/3` of pods and deployments
+fn is_not_ready(ready: &str) -> bool {
+    ready
+        .split_once('/')
+        .is_some_and(|(ready, wanted)| ready != wanted)
+}
+
+// `RESTARTS 4 (2m ago)`
+fn has_restarted(restarts: &str) -> bool {
+    restarts
+        .split_whitespace()
+        .next()
+        .and_then(|r| r.parse::<u32>().ok())
+        .is_some_and(|r| r > 0)
+}
+
+pub struct Kubectl {
+    confidence: Confidence,
+}
+impl Kubectl {
+    pub fn new(sample_lines: &Lines) -> Kubectl {
+        Kubectl {
+            confidence: match sample_lines.lines.first() {
+                Some(l) if is_header(&l.text) => Confidence::Certain,
+                _ => Confidence::Low,
+            },
+        }
+    }
+}
+impl Prioritizer for Kubectl {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let mut columns: Vec<(usize, String)> = Vec::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // `kubectl get all` prints one table per resource kind
+            let tier = if is_header(&line.text) {
+                columns = named_columns(&line.text);
+                TIER_HEADER
+            } else if line.text.trim().is_empty() {
+                TIER_HEALTHY
+            } else {
+                let column = |name| field(&line.text, &columns, name).unwrap_or_default();
+                let status = column("STATUS");
+                let failing = !status.is_empty() && !HEALTHY_STATUSES.contains(&status.as_str());
+                // finished jobs are never ready
+                let unhealthy = is_not_ready(&column("READY"))
+                    && !matches!(status.as_str(), "Completed" | "Succeeded");
+                match (failing, has_restarted(&column("RESTARTS"))) {
+                    (true, true) => TIER_CRASHING,
+                    (false, false) if !unhealthy => TIER_HEALTHY,
+                    _ => TIER_UNHEALTHY,
+                }
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PODS: &str = "NAME                     READY   STATUS             RESTARTS       AGE
+api-7d9f8c6b5d-abcde     1/1     Running            0              3d
+api-7d9f8c6b5d-fghij     0/1     CrashLoopBackOff   12 (2m ago)    3d
+migrate-x7k2p            0/1     Completed          0              3d
+worker-5f6d7c8b9-klmno   1/1     Running            2 (1h ago)     3d
+web-6c7d8e9f0-pqrst      0/1     Pending            0              5m
+";
+
+    #[gtest]
+    fn kubectl_get_pods() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PODS), 100, 20)?;
+        let p = Kubectl::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_HEALTHY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_CRASHING, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_HEALTHY, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_UNHEALTHY, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_UNHEALTHY, 5]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn kubectl_get_deployments() -> Result<()> {
+        let c = Cursor::new(
+            "NAME   READY   UP-TO-DATE   AVAILABLE   AGE\napi    2/3     3            2           3d\nweb    1/1     1            1           3d\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        Kubectl::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_UNHEALTHY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_HEALTHY, 2]));
+        Ok(())
+    }
+}
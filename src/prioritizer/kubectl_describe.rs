@@ -0,0 +1,149 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_IDENTITY: u32 = 0;
+const TIER_WARNING: u32 = 1;
+const TIER_FIELD: u32 = 2;
+const TIER_DETAIL: u32 = 3;
+const TIER_WALL: u32 = 4;
+
+const IDENTITY_FIELDS: [&str; 5] = ["Name", "Namespace", "Status", "Reason", "Message"];
+// long lists of key=value nobody reads
+const WALL_FIELDS: [&str; 4] = ["Labels", "Annotations", "Tolerations", "Node-Selectors"];
+
+// `Namespace:    default`, only the unindented ones
+fn top_level_field(text: &str) -> Option<&str> {
+    if text.starts_with(' ') {
+        return None;
+    }
+    let (key, _) = text.split_once(':')?;
+    if key.is_empty() || key.contains("  ") {
+        return None;
+    }
+    Some(key)
+}
+
+// `kubectl get events`: `LAST SEEN   TYPE      REASON    OBJECT    MESSAGE`
+fn is_events_header(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.contains(&"TYPE") && words.contains(&"REASON") && words.contains(&"MESSAGE")
+}
+
+pub struct KubectlDescribe {
+    confidence: Confidence,
+}
+impl KubectlDescribe {
+    pub fn new(sample_lines: &Lines) -> KubectlDescribe {
+        let describe = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| l.text.starts_with("Name:"))
+            && sample_lines
+                .lines
+                .iter()
+                .any(|l| l.text.starts_with("Namespace:") || l.text == "Events:");
+        let events = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| is_events_header(&l.text));
+        KubectlDescribe {
+            confidence: if describe || events {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for KubectlDescribe {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut field = "";
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let is_warning = line.text.split_whitespace().any(|w| w == "Warning");
+            let tier = if let Some(key) = top_level_field(&line.text) {
+                field = key;
+                if IDENTITY_FIELDS.contains(&key) || key == "Events" {
+                    TIER_IDENTITY
+                } else if WALL_FIELDS.contains(&key) {
+                    TIER_WALL
+                } else {
+                    TIER_FIELD
+                }
+            } else if is_events_header(&line.text) {
+                field = "Events";
+                TIER_IDENTITY
+            } else if WALL_FIELDS.contains(&field) {
+                TIER_WALL
+            } else if field == "Events" && is_warning {
+                TIER_WARNING
+            } else {
+                TIER_DETAIL
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const DESCRIBE: &str = "Name:         api-7d9f8c6b5d-fghij
+Namespace:    default
+Labels:       app=api
+              pod-template-hash=7d9f8c6b5d
+Status:       Running
+Containers:
+  api:
+    Image:    registry.local/api:1.4.2
+Events:
+  Type     Reason     Age   From               Message
+  ----     ------     ----  ----               -------
+  Normal   Scheduled  5m    default-scheduler  Successfully assigned default/api
+  Warning  BackOff    2m    kubelet            Back-off restarting failed container
+";
+
+    #[gtest]
+    fn kubectl_describe() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DESCRIBE), 100, 20)?;
+        let p = KubectlDescribe::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_IDENTITY, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_IDENTITY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_WALL, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_WALL, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_IDENTITY, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_FIELD, 5]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_DETAIL, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_IDENTITY, 8]));
+        expect_that!(&lines.lines[11].prio, eq(&vec![TIER_DETAIL, 11]));
+        expect_that!(&lines.lines[12].prio, eq(&vec![TIER_WARNING, 12]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn kubectl_get_events() -> Result<()> {
+        let c = Cursor::new(
+            "LAST SEEN   TYPE      REASON    OBJECT        MESSAGE\n2m          Normal    Pulled    pod/api-abc   Container image pulled\n1m          Warning   BackOff   pod/api-abc   Back-off restarting\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = KubectlDescribe::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_IDENTITY, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_DETAIL, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_WARNING, 2]));
+        Ok(())
+    }
+}
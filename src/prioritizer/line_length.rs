@@ -0,0 +1,78 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::{Result, bail};
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthPreference {
+    Shorter,
+    Longer,
+}
+impl FromStr for LengthPreference {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "short" | "shorter" => Ok(LengthPreference::Shorter),
+            "long" | "longer" => Ok(LengthPreference::Longer),
+            _ => bail!(
+                "unknown line length preference: {} (expected shorter or longer)",
+                s
+            ),
+        }
+    }
+}
+
+// never picked on its own, it breaks the ties left by another prioritizer
+pub struct LineLength {
+    confidence: Confidence,
+    preference: LengthPreference,
+}
+impl LineLength {
+    pub fn new(preference: LengthPreference) -> LineLength {
+        LineLength {
+            confidence: Confidence::Low,
+            preference,
+        }
+    }
+}
+impl Prioritizer for LineLength {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        for line in lines.lines.iter_mut() {
+            let length = line.text.trim_end().chars().count() as u32;
+            line.prio.push(match self.preference {
+                // blank lines are neither terse nor informative
+                _ if length == 0 => u32::MAX,
+                LengthPreference::Shorter => length,
+                LengthPreference::Longer => u32::MAX - 1 - length,
+            });
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn line_length_prioritizer() -> Result<()> {
+        let c = Cursor::new("ok\n\na much longer line\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        LineLength::new("shorter".parse()?).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![2]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![u32::MAX]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![18]));
+        LineLength::new(LengthPreference::Longer).prioritize(&mut lines)?;
+        expect_that!(lines.lines[2].prio[1], lt(lines.lines[0].prio[1]));
+        expect_that!(lines.lines[1].prio[1], eq(u32::MAX));
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use super::diagnostic::{Kind, Severity, kind};
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+use std::collections::HashSet;
+
+const TIER_FIRST_OCCURRENCE: u32 = 0;
+const TIER_REPEATED_OCCURRENCE: u32 = 1;
+const TIER_OTHER: u32 = 2;
+const TIER_FIRST_BODY: u32 = 3;
+const TIER_REPEATED_BODY: u32 = 4;
+
+// eslint style: `  12:5  error  'x' is defined but never used  no-unused-vars`
+fn eslint_rule(text: &str) -> Option<&str> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (line, column) = words.first()?.split_once(':')?;
+    if words.len() < 4
+        || line.parse::<u32>().is_err()
+        || column.parse::<u32>().is_err()
+        || !matches!(words[1], "error" | "warning")
+    {
+        return None;
+    }
+    words.last().copied()
+}
+
+// clippy names the lint in `#[warn(clippy::name)]` the first time, and in the
+// help url (`...index.html#name`) every time
+fn rustc_lint_name(text: &str) -> Option<&str> {
+    if let Some((_, rest)) = text.split_once("#[warn(").or(text.split_once("#[deny(")) {
+        return rest
+            .split(')')
+            .next()
+            .map(|n| n.trim_start_matches("clippy::"));
+    }
+    let (_, anchor) = text.split_once("index.html#")?;
+    anchor.split_whitespace().next()
+}
+
+fn is_rustc_header(text: &str) -> bool {
+    matches!(
+        kind(text),
+        Kind::Header(Severity::Error) | Kind::Header(Severity::Warning)
+    )
+}
+
+pub struct Lint {
+    confidence: Confidence,
+}
+impl Lint {
+    pub fn new(sample_lines: &Lines) -> Lint {
+        // clippy names itself in its notes, but so can a commit log; it has to
+        // come with rustc style diagnostics making up most of the sample
+        let names_clippy = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.contains("clippy::") || l.text.contains("rust-clippy"));
+        let is_clippy = names_clippy
+            && sample_lines.lines.iter().any(|l| is_rustc_header(&l.text))
+            && sample_lines
+                .lines
+                .iter()
+                .any(|l| matches!(kind(&l.text), Kind::Span))
+            && share_of_lines(sample_lines, |t| !matches!(kind(t), Kind::Other)) >= 0.5;
+        let confidence = if is_clippy {
+            Confidence::Certain
+        } else if sample_lines.lines.len() > 2
+            && share_of_lines(sample_lines, |t| eslint_rule(t).is_some()) >= 0.5
+        {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        Lint { confidence }
+    }
+
+    // lint key of each line: the eslint rule, or the name (or failing that,
+    // the message) of the rustc style block the line belongs to
+    fn keys(lines: &Lines) -> Vec<Option<String>> {
+        let mut keys: Vec<Option<String>> = vec![None; lines.lines.len()];
+        let mut block_start: Option<usize> = None;
+        let mut block_name: Option<String> = None;
+        for (line_number, line) in lines.lines.iter().enumerate() {
+            if let Some(rule) = eslint_rule(&line.text) {
+                keys[line_number] = Some(rule.to_string());
+                continue;
+            }
+            if is_rustc_header(&line.text) {
+                if let Some(start) = block_start {
+                    let key = block_name.take().unwrap_or(lines.lines[start].text.clone());
+                    keys[start..line_number].fill(Some(key));
+                }
+                block_start = Some(line_number);
+            } else if block_start.is_some() && block_name.is_none() {
+                block_name = rustc_lint_name(&line.text).map(String::from);
+            }
+        }
+        if let Some(start) = block_start {
+            let key = block_name.unwrap_or(lines.lines[start].text.clone());
+            keys[start..].fill(Some(key));
+        }
+        keys
+    }
+}
+impl Prioritizer for Lint {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let keys = Lint::keys(lines);
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut first_occurrence = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match &keys[line_number] {
+                Some(key) if eslint_rule(&line.text).is_some() => {
+                    if seen.insert(key) {
+                        TIER_FIRST_OCCURRENCE
+                    } else {
+                        TIER_REPEATED_OCCURRENCE
+                    }
+                }
+                Some(key) => {
+                    if is_rustc_header(&line.text) {
+                        first_occurrence = seen.insert(key);
+                    }
+                    match (kind(&line.text), first_occurrence) {
+                        (Kind::Header(_) | Kind::Span, true) => TIER_FIRST_OCCURRENCE,
+                        (Kind::Header(_) | Kind::Span, false) => TIER_REPEATED_OCCURRENCE,
+                        (_, true) => TIER_FIRST_BODY,
+                        (_, false) => TIER_REPEATED_BODY,
+                    }
+                }
+                // file names of eslint, summaries, compiler progress
+                None => TIER_OTHER,
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const CLIPPY: &str = "warning: called `.iter().count()` on a `Vec`
+   --> src/a.rs:115:23
+    |
+115 |         let n = v.iter().count();
+    |                 ^^^^^^^^^^^^^^^^ help: try: `.len()`
+    |
+    = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#iter_count
+    = note: `#[warn(clippy::iter_count)]` on by default
+warning: called `.iter().count()` on a `Vec`
+   --> src/a.rs:150:23
+    |
+    = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#iter_count
+warning: unused import: `Error`
+ --> src/b.rs:1:14
+";
+
+    #[gtest]
+    fn clippy_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(CLIPPY), 200, 20)?;
+        let p = Lint::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_FIRST_OCCURRENCE, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FIRST_OCCURRENCE, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_FIRST_BODY, 3]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_REPEATED_OCCURRENCE, 8]));
+        expect_that!(&lines.lines[10].prio, eq(&vec![TIER_REPEATED_BODY, 10]));
+        expect_that!(&lines.lines[12].prio, eq(&vec![TIER_FIRST_OCCURRENCE, 12]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn eslint_prioritizer() -> Result<()> {
+        let c = Cursor::new(
+            "/src/a.js\n  1:10  error  'foo' is unused  no-unused-vars\n  \
+             2:5  warning  Unexpected console  no-console\n  \
+             7:10  error  'bar' is unused  no-unused-vars\n",
+        );
+        let mut lines = Lines::from_reader(c, 200, 20)?;
+        let p = Lint::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_OTHER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FIRST_OCCURRENCE, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FIRST_OCCURRENCE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_REPEATED_OCCURRENCE, 3]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn lint_not_commit_log() -> Result<()> {
+        let c = Cursor::new(
+            "commit 3f2a1c9\nAuthor: someone\n\n    allow clippy::too_many_arguments in the parser\n\n\
+             commit 8e7d6b5\nAuthor: someone\n\n    fix: error: message wording\n",
+        );
+        let lines = Lines::from_reader(c, 200, 20)?;
+        expect_that!(u32::from(Lint::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use super::timestamp::find_timestamp;
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+// lower is more severe, so more important
+pub const SEVERITY_FATAL: u32 = 0;
+pub const SEVERITY_ERROR: u32 = 1;
+pub const SEVERITY_WARN: u32 = 2;
+pub const SEVERITY_INFO: u32 = 3;
+pub const SEVERITY_DEBUG: u32 = 4;
+pub const SEVERITY_TRACE: u32 = 5;
+
+// only the first words are considered, "INFO connection error" is INFO
+const WORDS_TO_SCAN: usize = 6;
+
+fn severity_of_word(word: &str) -> Option<u32> {
+    match word.to_ascii_uppercase().as_str() {
+        "FATAL" | "CRITICAL" | "CRIT" | "EMERG" | "EMERGENCY" | "ALERT" | "PANIC" => {
+            Some(SEVERITY_FATAL)
+        }
+        "ERROR" | "ERR" | "SEVERE" => Some(SEVERITY_ERROR),
+        "WARN" | "WARNING" => Some(SEVERITY_WARN),
+        "INFO" | "NOTICE" => Some(SEVERITY_INFO),
+        "DEBUG" | "DBG" | "FINE" => Some(SEVERITY_DEBUG),
+        "TRACE" | "FINER" | "FINEST" => Some(SEVERITY_TRACE),
+        _ => None,
+    }
+}
+
+// syslog priority prefix, e.g. `<3>` is err
+fn syslog_severity(text: &str) -> Option<u32> {
+    let pri: u32 = text.strip_prefix('<')?.split_once('>')?.0.parse().ok()?;
+    Some(match pri % 8 {
+        0..=2 => SEVERITY_FATAL,
+        3 => SEVERITY_ERROR,
+        4 => SEVERITY_WARN,
+        5 | 6 => SEVERITY_INFO,
+        _ => SEVERITY_DEBUG,
+    })
+}
+
+// severity token of a line: `ERROR ...`, `[warn] ...`, `level=info ...`, `<3>...`
+pub fn severity(text: &str) -> Option<u32> {
+    syslog_severity(text).or_else(|| {
+        text.split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|w| !w.is_empty())
+            .take(WORDS_TO_SCAN)
+            .find_map(severity_of_word)
+    })
+}
+
+// severity a line starts with, as log lines do: `ERROR ...`, `[warn] ...`,
+// `level=info ...`, `<3>...`, but not `name: error-handler`
+pub fn leading_severity(text: &str) -> Option<u32> {
+    syslog_severity(text).or_else(|| {
+        let mut words = text
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|w| !w.is_empty());
+        match words.next()? {
+            "level" | "lvl" => severity_of_word(words.next()?),
+            word => severity_of_word(word),
+        }
+    })
+}
+
+pub struct LogLevel {
+    confidence: Confidence,
+}
+impl LogLevel {
+    pub fn new(sample_lines: &Lines) -> LogLevel {
+        let share = share_of_lines(sample_lines, |t| severity(t).is_some());
+        LogLevel {
+            confidence: if sample_lines.lines.len() > 2 && share >= 0.5 {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for LogLevel {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let len: usize = lines.lines.len();
+        // with timestamps, the most recent lines of a severity come first
+        let mut last_timestamp = None;
+        let timestamps: Vec<Option<u64>> = lines
+            .lines
+            .iter()
+            .map(|l| {
+                last_timestamp = find_timestamp(&l.text).or(last_timestamp);
+                last_timestamp
+            })
+            .collect();
+        let newest = timestamps.iter().flatten().copied().max();
+        // continuation lines (stack traces, wrapped messages) share the
+        // severity of the line they follow
+        let mut last_severity = SEVERITY_INFO;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            last_severity = severity(&line.text).unwrap_or(last_severity);
+            line.prio.push(last_severity);
+            if let Some(newest) = newest {
+                let age = timestamps[line_number]
+                    .map_or(u32::MAX, |t| (newest - t).min(u32::MAX as u64 - 1) as u32);
+                line.prio.push(age);
+            }
+            line.prio
+                .push(line_number.min(len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn severity_tokens() {
+        expect_that!(severity("ERROR something broke"), some(eq(SEVERITY_ERROR)));
+        expect_that!(severity("[warn] disk almost full"), some(eq(SEVERITY_WARN)));
+        expect_that!(
+            severity("2025-06-01T10:00:00Z level=debug msg=hello"),
+            some(eq(SEVERITY_DEBUG))
+        );
+        expect_that!(severity("<3>kernel: oops"), some(eq(SEVERITY_ERROR)));
+        expect_that!(severity("<14>app: started"), some(eq(SEVERITY_INFO)));
+        expect_that!(
+            severity("INFO retrying after error"),
+            some(eq(SEVERITY_INFO))
+        );
+        expect_that!(severity("just some text"), none());
+    }
+
+    #[gtest]
+    fn log_level_prioritizer() -> Result<()> {
+        //                   0            1             2         3            4
+        let c = Cursor::new("INFO start\nDEBUG tick\nERROR boom\n  at frame\nTRACE tock\n");
+        let mut lines = Lines::from_reader(c, 40, 20)?;
+        let p = LogLevel::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![SEVERITY_INFO, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![SEVERITY_DEBUG, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![SEVERITY_ERROR, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![SEVERITY_ERROR, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![SEVERITY_TRACE, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn log_level_blends_recency() -> Result<()> {
+        let c = Cursor::new(
+            "2025-06-01T10:00:00Z ERROR old\n\
+             2025-06-01T10:00:05Z INFO tick\n\
+             2025-06-01T10:00:09Z ERROR recent\n\
+             2025-06-01T10:00:10Z INFO done\n",
+        );
+        let mut lines = Lines::from_reader(c, 60, 20)?;
+        LogLevel::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![SEVERITY_ERROR, 10, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![SEVERITY_ERROR, 1, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![SEVERITY_INFO, 0, 0]));
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use super::timestamp::MONTHS;
+use super::units::parse_size;
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::{Result, bail};
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+const TIER_TOTAL: u32 = 0;
+const TIER_FIRST: u32 = 1;
+const TIER_ENTRY: u32 = 2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LsCriterion {
+    #[default]
+    Size,
+    Mtime,
+    DirectoriesFirst,
+}
+impl FromStr for LsCriterion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "size" => Ok(LsCriterion::Size),
+            "mtime" | "time" => Ok(LsCriterion::Mtime),
+            "dirs" | "directories" => Ok(LsCriterion::DirectoriesFirst),
+            _ => bail!("unknown ls criterion: {} (expected size, mtime or dirs)", s),
+        }
+    }
+}
+
+struct Entry {
+    is_directory: bool,
+    size: f64,
+    // (year, month, day, minutes), recent files show a time instead of a year
+    mtime: (u32, u32, u32, u32),
+}
+
+fn minutes(time: &str) -> Option<u32> {
+    let (h, m) = time.split_once(':')?;
+    Some(h.parse::<u32>().ok()? * 60 + m.parse::<u32>().ok()?)
+}
+
+// `drwxr-xr-x 2 user group 4096 Jun  1 10:00 name`, also with `-h` sizes
+// and `--time-style=long-iso` dates
+fn parse_entry(text: &str) -> Option<Entry> {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    let permissions = fields.first()?;
+    if permissions.len() < 10
+        || !permissions.starts_with(['-', 'd', 'l', 'c', 'b', 'p', 's'])
+        || !permissions
+            .get(1..10)
+            .is_some_and(|p| p.chars().all(|c| "rwxsStT-".contains(c)))
+        || fields.len() < 8
+    {
+        return None;
+    }
+    let size = parse_size(fields[4])?;
+    let mtime = if let Some((year, date)) = fields[5].split_once('-') {
+        let (month, day) = date.split_once('-')?;
+        (
+            year.parse().ok()?,
+            month.parse().ok()?,
+            day.parse().ok()?,
+            minutes(fields[6])?,
+        )
+    } else {
+        let month = MONTHS.iter().position(|m| *m == fields[5])? as u32 + 1;
+        let day = fields[6].parse().ok()?;
+        match minutes(fields[7]) {
+            Some(minutes) => (u32::MAX, month, day, minutes),
+            None => (fields[7].parse().ok()?, month, day, 0),
+        }
+    };
+    Some(Entry {
+        is_directory: permissions.starts_with('d'),
+        size,
+        mtime,
+    })
+}
+
+pub struct Ls {
+    confidence: Confidence,
+    criterion: LsCriterion,
+}
+impl Ls {
+    pub fn new(sample_lines: &Lines, criterion: LsCriterion) -> Ls {
+        Ls {
+            confidence: if sample_lines.lines.len() > 1
+                && share_of_lines(sample_lines, |t| {
+                    parse_entry(t).is_some() || t.starts_with("total ")
+                }) >= 0.8
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+            criterion,
+        }
+    }
+
+    fn compare(&self, a: &Entry, b: &Entry) -> Ordering {
+        match self.criterion {
+            LsCriterion::Size => b.size.total_cmp(&a.size),
+            LsCriterion::Mtime => b.mtime.cmp(&a.mtime),
+            LsCriterion::DirectoriesFirst => Ordering::Equal,
+        }
+    }
+}
+impl Prioritizer for Ls {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let entries: Vec<Option<Entry>> =
+            lines.lines.iter().map(|l| parse_entry(&l.text)).collect();
+        let mut order: Vec<usize> = (0..entries.len())
+            .filter(|i| entries[*i].is_some())
+            .collect();
+        // stable, so equal entries stay in listing order
+        order.sort_by(|a, b| {
+            self.compare(entries[*a].as_ref().unwrap(), entries[*b].as_ref().unwrap())
+        });
+        let mut ranks: Vec<u32> = vec![0; entries.len()];
+        for (rank, line_number) in order.into_iter().enumerate() {
+            ranks[line_number] = rank as u32;
+        }
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match &entries[line_number] {
+                // `total 42` and the `dir:` headers of multi directory listings
+                None => TIER_TOTAL,
+                Some(entry)
+                    if entry.is_directory && self.criterion == LsCriterion::DirectoriesFirst =>
+                {
+                    TIER_FIRST
+                }
+                Some(_) => TIER_ENTRY,
+            };
+            line.prio.push(tier);
+            line.prio.push(ranks[line_number]);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const LS: &str = "total 24
+-rw-r--r-- 1 me me  120 Jun  1  2023 old.txt
+drwxr-xr-x 2 me me 4096 Jun  1 10:00 src
+-rw-r--r-- 1 me me 9000 May 30 09:00 big.bin
+lrwxrwxrwx 1 me me    7 Jun  2 11:00 link -> src
+";
+
+    fn prioritized(criterion: LsCriterion) -> Result<Lines> {
+        let mut lines = Lines::from_reader(Cursor::new(LS), 100, 20)?;
+        let p = Ls::new(&lines, criterion);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        Ok(lines)
+    }
+
+    #[gtest]
+    fn ls_by_size() -> Result<()> {
+        let lines = prioritized(LsCriterion::Size)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_TOTAL, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ENTRY, 2]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_ENTRY, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ENTRY, 0]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ENTRY, 3]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn ls_by_mtime() -> Result<()> {
+        let lines = prioritized(LsCriterion::Mtime)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ENTRY, 3]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_ENTRY, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ENTRY, 2]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ENTRY, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn ls_directories_first() -> Result<()> {
+        let lines = prioritized(LsCriterion::DirectoriesFirst)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ENTRY, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FIRST, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ENTRY, 2]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn ls_multibyte_first_word() -> Result<()> {
+        let lines = Lines::from_reader(Cursor::new("dééééééé x y z a b c d\n"), 100, 20)?;
+        expect_that!(parse_entry(&lines.lines[0].text).is_none(), eq(true));
+        let p = Ls::new(&lines, LsCriterion::Size);
+        expect_that!(u32::from(p.confidence()), eq(10));
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+use std::collections::HashSet;
+
+const TIER_HEADER: u32 = 0;
+const TIER_PROCESS: u32 = 1;
+const TIER_FILE_TYPE: u32 = 2;
+const TIER_REPEATED: u32 = 3;
+
+fn is_header(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.starts_with(&["COMMAND", "PID"]) && words.contains(&"FD") && words.contains(&"TYPE")
+}
+
+// `cwd`, `txt`, `mem` are kept as is, numbered descriptors (`3u`, `12r`) are
+// all alike
+fn fd_kind(fd: &str) -> &str {
+    if fd.starts_with(|c: char| c.is_ascii_digit()) {
+        "fd"
+    } else {
+        fd
+    }
+}
+
+pub struct Lsof {
+    confidence: Confidence,
+}
+impl Lsof {
+    pub fn new(sample_lines: &Lines) -> Lsof {
+        Lsof {
+            confidence: match sample_lines.lines.first() {
+                Some(l) if is_header(&l.text) => Confidence::Certain,
+                _ => Confidence::Low,
+            },
+        }
+    }
+}
+impl Prioritizer for Lsof {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let header: Vec<String> = match lines.lines.iter().find(|l| is_header(&l.text)) {
+            Some(l) => l.text.split_whitespace().map(String::from).collect(),
+            None => Vec::new(),
+        };
+        let column = |name: &str| header.iter().position(|h| h == name);
+        let (fd_column, type_column) = (column("FD"), column("TYPE"));
+        let mut processes: HashSet<String> = HashSet::new();
+        // the shared libraries of a process all are `mem REG`, one of them is enough
+        let mut file_types: HashSet<(String, String, String)> = HashSet::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let words: Vec<&str> = line.text.split_whitespace().collect();
+            let field = |c: Option<usize>| c.and_then(|c| words.get(c)).copied().unwrap_or("");
+            let pid = words.get(1).copied().unwrap_or("").to_string();
+            let file_type = (
+                pid.clone(),
+                fd_kind(field(fd_column)).to_string(),
+                field(type_column).to_string(),
+            );
+            let tier = if is_header(&line.text) {
+                TIER_HEADER
+            } else if processes.insert(pid) {
+                file_types.insert(file_type);
+                TIER_PROCESS
+            } else if file_types.insert(file_type) {
+                TIER_FILE_TYPE
+            } else {
+                TIER_REPEATED
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const LSOF: &str = "COMMAND  PID USER   FD   TYPE DEVICE SIZE/OFF    NODE NAME
+sshd     812 root  cwd    DIR  259,2     4096       2 /
+sshd     812 root  mem    REG  259,2   141872 1311023 /usr/lib/libz.so.1
+sshd     812 root  mem    REG  259,2  2029592 1311001 /usr/lib/libc.so.6
+sshd     812 root    3u  IPv4  21011      0t0     TCP *:ssh (LISTEN)
+nginx    901 www   txt    REG  259,2  1190008 1052981 /usr/sbin/nginx
+";
+
+    #[gtest]
+    fn lsof_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(LSOF), 100, 20)?;
+        let p = Lsof::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_PROCESS, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FILE_TYPE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_REPEATED, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_FILE_TYPE, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_PROCESS, 5]));
+        Ok(())
+    }
+}
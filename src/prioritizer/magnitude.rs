@@ -0,0 +1,144 @@
+use super::units::{parse_duration, parse_size};
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_NUMBER: u32 = 0;
+const TIER_NO_NUMBER: u32 = 1;
+
+// lines with a number, and how far apart the numbers must be to be worth
+// ranking
+const MIN_SHARE: f32 = 0.9;
+const MIN_SPREAD: f64 = 10.0;
+
+// `532K`, `12ms`, `1,024` and `12 MB` (with `next` the following word).
+// sizes are in bytes and durations in seconds, both are only ever compared
+// with numbers of their own kind in practice. times of day are not
+// magnitudes
+fn magnitude(word: &str, next: Option<&str>) -> Option<f64> {
+    let word = word.trim_matches(|c: char| "()[]{},;\"'".contains(c));
+    if word.contains(':') {
+        return None;
+    }
+    let parse = |s: &str| parse_size(s).or_else(|| parse_duration(s));
+    match next {
+        Some(unit)
+            if word
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.' || c == ',') =>
+        {
+            parse(&format!("{}{}", word, unit.trim_end_matches([',', ')']))).or_else(|| parse(word))
+        }
+        _ => parse(word),
+    }
+}
+
+// the biggest number on the line
+fn line_magnitude(text: &str) -> Option<f64> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    (0..words.len())
+        .filter_map(|i| magnitude(words[i], words.get(i + 1).copied()))
+        .max_by(|a, b| a.total_cmp(b))
+}
+
+pub struct Magnitude {
+    confidence: Confidence,
+}
+impl Magnitude {
+    pub fn new(sample_lines: &Lines) -> Magnitude {
+        let magnitudes: Vec<f64> = sample_lines
+            .lines
+            .iter()
+            .filter_map(|l| line_magnitude(&l.text))
+            .collect();
+        let smallest = magnitudes.iter().copied().fold(f64::INFINITY, f64::min);
+        let biggest = magnitudes.iter().copied().fold(0.0, f64::max);
+        Magnitude {
+            confidence: if sample_lines.lines.len() > 4
+                && share_of_lines(sample_lines, |t| line_magnitude(t).is_some()) >= MIN_SHARE
+                && biggest >= smallest.max(1.0) * MIN_SPREAD
+            {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Magnitude {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let magnitudes: Vec<Option<f64>> = lines
+            .lines
+            .iter()
+            .map(|l| line_magnitude(&l.text))
+            .collect();
+        let mut sorted: Vec<f64> = magnitudes.iter().flatten().copied().collect();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            match magnitudes[line_number] {
+                Some(magnitude) => {
+                    // number of lines with a bigger number than this one
+                    let rank = sorted.partition_point(|m| *m > magnitude);
+                    line.prio.push(TIER_NUMBER);
+                    line.prio.push(rank as u32);
+                }
+                None => {
+                    line.prio.push(TIER_NO_NUMBER);
+                    line.prio.push(0);
+                }
+            }
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TRANSFERS: &str = "uploaded a.tar 1.4G
+uploaded b.txt 532K
+uploaded c.log 12 MB
+skipped d.tmp
+uploaded e.bin 3,000 bytes
+uploaded f.iso 4.7G
+";
+
+    #[gtest]
+    fn magnitude_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TRANSFERS), 100, 20)?;
+        // one line in six without a number is too many
+        expect_that!(u32::from(Magnitude::new(&lines).confidence()), eq(10));
+        let sample = Lines::from_reader(
+            Cursor::new(TRANSFERS.replace("skipped d.tmp\n", "")),
+            100,
+            20,
+        )?;
+        expect_that!(u32::from(Magnitude::new(&sample).confidence()), eq(20));
+        Magnitude::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_NUMBER, 0, 5]));
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_NUMBER, 1, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_NUMBER, 2, 2]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_NUMBER, 3, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_NUMBER, 4, 4]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_NO_NUMBER, 0, 3]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn magnitude_ignores_dates_and_versions() {
+        expect_that!(magnitude("2026-10-16", None), none());
+        expect_that!(magnitude("12:30:01", None), none());
+        expect_that!(magnitude("1.2.3", None), none());
+        expect_that!(magnitude("(12ms)", None), some(eq(0.012)));
+    }
+}
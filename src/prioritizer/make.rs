@@ -0,0 +1,138 @@
+use super::diagnostic::{Kind, Severity, kind};
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_WARNING: u32 = 1;
+const TIER_TRANSITION: u32 = 2;
+const TIER_CONTEXT: u32 = 3;
+const TIER_COMMAND: u32 = 4;
+
+const COMPILERS: [&str; 8] = ["cc", "gcc", "g++", "c++", "clang", "clang++", "ld", "ar"];
+
+// `make[2]: ...` or `make: ...`
+fn make_message(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("make")?;
+    let rest = match rest.strip_prefix('[') {
+        Some(r) => r.split_once(']')?.1,
+        None => rest,
+    };
+    rest.strip_prefix(": ")
+}
+
+// cmake's `[ 42%] Building C object ...`
+fn progress_message(text: &str) -> Option<&str> {
+    let (percent, message) = text.strip_prefix('[')?.split_once("%] ")?;
+    percent.trim().parse::<u32>().ok()?;
+    Some(message)
+}
+
+// `/usr/bin/cc -O2 -c a.c`, also cross compilers like `arm-none-eabi-gcc`
+fn is_compiler_invocation(text: &str) -> bool {
+    let Some(program) = text.split_whitespace().next() else {
+        return false;
+    };
+    let program = program.rsplit('/').next().unwrap_or(program);
+    COMPILERS
+        .iter()
+        .any(|c| program == *c || program.ends_with(&format!("-{}", c)))
+}
+
+fn tier(text: &str) -> u32 {
+    if let Some(message) = make_message(text) {
+        if message.starts_with("***") {
+            TIER_ERROR
+        } else {
+            TIER_TRANSITION
+        }
+    } else if let Some(message) = progress_message(text) {
+        if message.starts_with("Building") {
+            TIER_COMMAND
+        } else {
+            // `Linking ...`, `Built target ...`
+            TIER_TRANSITION
+        }
+    } else if text.starts_with("CMake Error") || text.contains("undefined reference to") {
+        TIER_ERROR
+    } else if text.starts_with("CMake Warning") {
+        TIER_WARNING
+    } else if is_compiler_invocation(text) {
+        TIER_COMMAND
+    } else {
+        match kind(text) {
+            Kind::Header(Severity::Error) => TIER_ERROR,
+            Kind::Header(Severity::Warning) => TIER_WARNING,
+            _ if text.starts_with("-- ") => TIER_COMMAND,
+            _ => TIER_CONTEXT,
+        }
+    }
+}
+
+pub struct Make {
+    confidence: Confidence,
+}
+impl Make {
+    pub fn new(sample_lines: &Lines) -> Make {
+        Make {
+            confidence: if sample_lines.lines.iter().any(|l| {
+                make_message(&l.text).is_some()
+                    || progress_message(&l.text).is_some()
+                    || l.text.starts_with("-- Build files have been written")
+            }) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Make {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tier(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const MAKE: &str = "make[1]: Entering directory '/src/foo'
+[ 42%] Building C object src/CMakeFiles/foo.dir/a.c.o
+/usr/bin/cc -DFOO -I/src/include -O2 -o CMakeFiles/foo.dir/a.c.o -c /src/a.c
+/src/a.c:12:5: warning: unused variable 'x' [-Wunused-variable]
+   12 |     int x;
+/src/a.c:20:1: error: expected ';' before '}' token
+make[2]: *** [src/CMakeFiles/foo.dir/build.make:76: a.c.o] Error 1
+[100%] Built target bar
+";
+
+    #[gtest]
+    fn make_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(MAKE), 100, 20)?;
+        let p = Make::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_TRANSITION, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_COMMAND, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_COMMAND, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_WARNING, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_CONTEXT, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ERROR, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_ERROR, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_TRANSITION, 7]));
+        Ok(())
+    }
+}
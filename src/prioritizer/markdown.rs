@@ -0,0 +1,189 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+// headings take 0 to 5 according to their level
+const TIER_SECTION_START: u32 = 6;
+const TIER_LIST_ITEM: u32 = 7;
+const TIER_OTHER: u32 = 8;
+const TIER_CODE: u32 = 9;
+
+// `## Title` is level 2
+fn heading_level(text: &str) -> Option<u32> {
+    let hashes = text.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes) && text[hashes..].starts_with(' ') {
+        Some(hashes as u32)
+    } else {
+        None
+    }
+}
+
+// setext headings are underlined, `===` for level 1 and `---` for level 2
+fn underline_level(text: &str) -> Option<u32> {
+    let text = text.trim_end();
+    if text.len() >= 2 && text.chars().all(|c| c == '=') {
+        Some(1)
+    } else if text.len() >= 2 && text.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn is_fence(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("```") || text.starts_with("~~~")
+}
+
+fn is_list_item(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("- ")
+        || text.starts_with("* ")
+        || text.starts_with("+ ")
+        || text
+            .split_once(". ")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+// a sentence, as opposed to code or `key = value` under `# comment` lines
+fn is_prose(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_alphabetic())
+        && text.split_whitespace().count() >= 4
+        && !text.contains(['=', ';', '{', '}', '(', ')', '$', '|'])
+}
+
+pub struct Markdown {
+    confidence: Confidence,
+}
+impl Markdown {
+    pub fn new(sample_lines: &Lines) -> Markdown {
+        let levels: Vec<u32> = sample_lines
+            .lines
+            .iter()
+            .filter_map(|l| heading_level(&l.text))
+            .collect();
+        let n_headings = levels.len();
+        // shell and toml comments look like level 1 headings, so a document
+        // also has deeper headings, or text and lists under its headings
+        let is_document = levels.iter().any(|level| *level != levels[0])
+            || sample_lines
+                .lines
+                .iter()
+                .any(|l| is_prose(&l.text) || is_list_item(&l.text));
+        let starts_with_heading = sample_lines
+            .lines
+            .iter()
+            .find(|l| !l.text.trim().is_empty())
+            .is_some_and(|l| heading_level(&l.text).is_some());
+        Markdown {
+            confidence: if !is_document {
+                Confidence::Low
+            } else if starts_with_heading && n_headings >= 2 {
+                Confidence::High
+            } else if n_headings >= 2 {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Markdown {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut tiers: Vec<u32> = Vec::with_capacity(lines.lines.len());
+        let mut in_code = false;
+        let mut section_started = true;
+        for (line_number, line) in lines.lines.iter().enumerate() {
+            let text = &line.text;
+            let previous_is_text = line_number > 0
+                && !lines.lines[line_number - 1].text.trim().is_empty()
+                && tiers[line_number - 1] >= TIER_SECTION_START;
+            let tier = if is_fence(text) {
+                in_code = !in_code;
+                TIER_LIST_ITEM
+            } else if in_code {
+                TIER_CODE
+            } else if let Some(level) = heading_level(text) {
+                section_started = false;
+                level - 1
+            } else if let Some(level) = underline_level(text).filter(|_| previous_is_text) {
+                tiers[line_number - 1] = level - 1;
+                section_started = false;
+                level - 1
+            } else if text.trim().is_empty() {
+                TIER_OTHER
+            } else if !section_started {
+                section_started = true;
+                TIER_SECTION_START
+            } else if is_list_item(text) {
+                TIER_LIST_ITEM
+            } else {
+                TIER_OTHER
+            };
+            tiers.push(tier);
+        }
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tiers[line_number]);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const README: &str = "# oneless
+
+like cat, but shorter
+more text
+
+## Usage
+- first
+- second
+```bash
+find . | oneless
+```
+Changelog
+---------
+";
+
+    #[gtest]
+    fn markdown_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(README), 100, 20)?;
+        let p = Markdown::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_SECTION_START, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_OTHER, 3]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![1, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_SECTION_START, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_LIST_ITEM, 7]));
+        expect_that!(&lines.lines[9].prio, eq(&vec![TIER_CODE, 9]));
+        expect_that!(&lines.lines[11].prio, eq(&vec![1, 11]));
+        expect_that!(&lines.lines[12].prio, eq(&vec![1, 12]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn markdown_not_comments() -> Result<()> {
+        let c = Cursor::new(
+            "# the address to listen on\nhost = \"localhost\"\n\n# port, 0 for any free port\nport = 8080\n",
+        );
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Markdown::new(&lines).confidence()), eq(10));
+        let c = Cursor::new("# Notes\nsome of the things to do\n# Done\nnothing so far\n");
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Markdown::new(&lines).confidence()), eq(30));
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_MOUNTED: u32 = 1;
+const TIER_DEVICE: u32 = 2;
+const TIER_PSEUDO: u32 = 3;
+
+// filesystems with no disk behind them, and the read-only images snaps and
+// containers are made of
+const PSEUDO_FILESYSTEMS: [&str; 25] = [
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "tmpfs",
+    "cgroup",
+    "cgroup2",
+    "securityfs",
+    "pstore",
+    "bpf",
+    "debugfs",
+    "tracefs",
+    "mqueue",
+    "hugetlbfs",
+    "configfs",
+    "fusectl",
+    "binfmt_misc",
+    "autofs",
+    "overlay",
+    "nsfs",
+    "squashfs",
+    "efivarfs",
+    "ramfs",
+    "rpc_pipefs",
+    "selinuxfs",
+];
+
+// `/dev/sda2 on / type ext4 (rw,relatime)`
+fn mount_type(text: &str) -> Option<(&str, &str)> {
+    let (source, rest) = text.split_once(" on ")?;
+    let (_, rest) = rest.split_once(" type ")?;
+    Some((source, rest.split_whitespace().next()?))
+}
+
+fn mount_tier(source: &str, fs_type: &str) -> u32 {
+    if PSEUDO_FILESYSTEMS.contains(&fs_type) || source.starts_with("/dev/loop") {
+        TIER_PSEUDO
+    } else {
+        TIER_MOUNTED
+    }
+}
+
+// `NAME MAJ:MIN RM SIZE RO TYPE MOUNTPOINTS`, the values have no spaces
+fn lsblk_columns(header: &str) -> Option<(usize, usize)> {
+    let names: Vec<&str> = header.split_whitespace().collect();
+    if names.first() != Some(&"NAME") {
+        return None;
+    }
+    let device_type = names.iter().position(|n| *n == "TYPE")?;
+    let mountpoint = names.iter().position(|n| n.starts_with("MOUNTPOINT"))?;
+    Some((device_type, mountpoint))
+}
+
+fn lsblk_tier(text: &str, device_type: usize, mountpoint: usize) -> u32 {
+    let fields: Vec<&str> = text.split_whitespace().collect();
+    match fields.get(device_type) {
+        Some(&"loop") => TIER_PSEUDO,
+        _ if fields.get(mountpoint).is_some() => TIER_MOUNTED,
+        _ => TIER_DEVICE,
+    }
+}
+
+pub struct Mounts {
+    confidence: Confidence,
+}
+impl Mounts {
+    pub fn new(sample_lines: &Lines) -> Mounts {
+        let lsblk = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| lsblk_columns(&l.text).is_some());
+        Mounts {
+            confidence: if lsblk || share_of_lines(sample_lines, |t| mount_type(t).is_some()) >= 0.9
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Mounts {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let lsblk = lines.lines.first().and_then(|l| lsblk_columns(&l.text));
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match (lsblk, mount_type(&line.text)) {
+                (Some(_), _) if line_number == 0 => TIER_HEADER,
+                (Some((device_type, mountpoint)), _) => {
+                    lsblk_tier(&line.text, device_type, mountpoint)
+                }
+                (None, Some((source, fs_type))) => mount_tier(source, fs_type),
+                (None, None) => TIER_DEVICE,
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const LSBLK: &str = "NAME        MAJ:MIN RM   SIZE RO TYPE MOUNTPOINTS
+loop0         7:0    0  55.7M  1 loop /snap/core18/2829
+sda           8:0    0 476.9G  0 disk
+├─sda1        8:1    0   512M  0 part /boot/efi
+└─sda2        8:2    0 476.4G  0 part /
+";
+
+    #[gtest]
+    fn mounts_lsblk() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(LSBLK), 100, 20)?;
+        let p = Mounts::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_PSEUDO, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_DEVICE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_MOUNTED, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_MOUNTED, 4]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn mounts_mount() -> Result<()> {
+        let c = Cursor::new(
+            "/dev/sda2 on / type ext4 (rw,relatime)\nproc on /proc type proc (rw,nosuid)\noverlay on /var/lib/docker/overlay2/x/merged type overlay (rw)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Mounts::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_MOUNTED, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_PSEUDO, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PSEUDO, 2]));
+        Ok(())
+    }
+}
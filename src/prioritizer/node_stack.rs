@@ -0,0 +1,141 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_APPLICATION_FRAME: u32 = 1;
+const TIER_OTHER: u32 = 2;
+const TIER_DEPENDENCY_FRAME: u32 = 3;
+const TIER_INTERNAL_FRAME: u32 = 4;
+
+const SOURCE_MARKERS: [&str; 9] = [
+    ".js:",
+    ".mjs:",
+    ".cjs:",
+    ".jsx:",
+    ".ts:",
+    ".tsx:",
+    "node:",
+    "<anonymous>",
+    "(index ",
+];
+
+enum Frame {
+    Application,
+    Dependency,
+    Internal,
+}
+
+// `    at foo (/app/src/index.js:10:11)`
+fn frame(text: &str) -> Option<Frame> {
+    let location = text.trim_start().strip_prefix("at ")?;
+    if !SOURCE_MARKERS.iter().any(|m| location.contains(m)) {
+        None
+    } else if location.contains("node:") || location.contains("(internal/") {
+        Some(Frame::Internal)
+    } else if location.contains("node_modules") {
+        Some(Frame::Dependency)
+    } else {
+        Some(Frame::Application)
+    }
+}
+
+// `Error: boom`, `TypeError [ERR_INVALID_ARG_TYPE]: ...`, `Uncaught RangeError: ...`
+fn is_error_message(text: &str) -> bool {
+    let text = text.strip_prefix("Uncaught ").unwrap_or(text);
+    let name = text.split([':', ' ']).next().unwrap_or("");
+    !name.contains('.') && (name.ends_with("Error") || name.ends_with("Exception"))
+}
+
+pub struct NodeStackTrace {
+    confidence: Confidence,
+}
+impl NodeStackTrace {
+    pub fn new(sample_lines: &Lines) -> NodeStackTrace {
+        let share = share_of_lines(sample_lines, |t| frame(t).is_some());
+        let has_error = sample_lines.lines.iter().any(|l| is_error_message(&l.text));
+        NodeStackTrace {
+            confidence: if has_error && share >= 0.3 {
+                Confidence::Certain
+            } else if share >= 0.3 {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for NodeStackTrace {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut depth: u32 = 0;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match frame(&line.text) {
+                Some(frame) => {
+                    depth += 1;
+                    match frame {
+                        Frame::Application => TIER_APPLICATION_FRAME,
+                        Frame::Dependency => TIER_DEPENDENCY_FRAME,
+                        Frame::Internal => TIER_INTERNAL_FRAME,
+                    }
+                }
+                None if is_error_message(&line.text) => {
+                    depth = 0;
+                    TIER_ERROR
+                }
+                None => TIER_OTHER,
+            };
+            line.prio.push(tier);
+            line.prio.push(depth);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TRACE: &str = "TypeError: Cannot read properties of undefined (reading 'x')
+    at render (/app/src/view.js:10:11)
+    at Layer.handle (/app/node_modules/express/lib/router/layer.js:95:5)
+    at Module._compile (node:internal/modules/cjs/loader:1105:14)
+    at async main (/app/src/index.ts:3:1)
+";
+
+    #[gtest]
+    fn node_stack_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TRACE), 100, 20)?;
+        let p = NodeStackTrace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_ERROR, 0, 0]));
+        expect_that!(
+            &lines.lines[1].prio,
+            eq(&vec![TIER_APPLICATION_FRAME, 1, 1])
+        );
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_DEPENDENCY_FRAME, 2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_INTERNAL_FRAME, 3, 3]));
+        expect_that!(
+            &lines.lines[4].prio,
+            eq(&vec![TIER_APPLICATION_FRAME, 4, 4])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn error_messages() {
+        expect_that!(is_error_message("Error: boom"), eq(true));
+        expect_that!(is_error_message("Uncaught RangeError: x"), eq(true));
+        expect_that!(is_error_message("TypeError [ERR_X]: y"), eq(true));
+        expect_that!(is_error_message("java.io.IOException: y"), eq(false));
+        expect_that!(is_error_message("no Error here"), eq(false));
+    }
+}
@@ -0,0 +1,179 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_VERIFY: u32 = 0;
+const TIER_IDENTITY: u32 = 1;
+const TIER_PROTOCOL: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_BLOB: u32 = 4;
+
+const VERIFY_PREFIXES: [&str; 3] = ["Verification", "Verify return code", "verify error"];
+// s_client's chain (`s:`, `i:`, `v:`) and summary, and x509 -text's fields
+const IDENTITY_PREFIXES: [&str; 11] = [
+    "s:",
+    "i:",
+    "v:",
+    "subject=",
+    "issuer=",
+    "Subject:",
+    "Issuer:",
+    "Not Before",
+    "Not After",
+    "DNS:",
+    "X509v3 Subject Alternative Name",
+];
+const PROTOCOL_PREFIXES: [&str; 5] = [
+    "Protocol",
+    "Cipher",
+    "New, ",
+    "Server public key",
+    "Peer signing",
+];
+
+fn is_header(text: &str) -> bool {
+    text.starts_with("CONNECTED(")
+        || text.starts_with("-----BEGIN CERTIFICATE-----")
+        || text.trim() == "Certificate:"
+}
+
+// `        00:a1:b2:...` in x509 -text, `    0000 - 1f 8b ...` in session tickets
+fn is_hex_dump(text: &str) -> bool {
+    let trimmed = text.trim();
+    let colon_hex = trimmed.len() >= 6
+        && trimmed
+            .split(':')
+            .filter(|b| !b.is_empty())
+            .all(|b| b.len() == 2 && b.chars().all(|c| c.is_ascii_hexdigit()));
+    let offset_hex = trimmed
+        .split_once(" - ")
+        .is_some_and(|(o, _)| o.len() == 4 && o.chars().all(|c| c.is_ascii_hexdigit()));
+    colon_hex || offset_hex
+}
+
+fn tier(text: &str) -> u32 {
+    // the chain lines are indented by their depth, ` 0 s:CN = example.com`
+    let trimmed = text
+        .trim_start()
+        .trim_start_matches(|c: char| c.is_ascii_digit());
+    let trimmed = trimmed.trim_start();
+    if VERIFY_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+        || (trimmed.starts_with("verify return:") && trimmed != "verify return:1")
+    {
+        TIER_VERIFY
+    } else if IDENTITY_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+        TIER_IDENTITY
+    } else if PROTOCOL_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+        TIER_PROTOCOL
+    } else if is_hex_dump(text) {
+        TIER_BLOB
+    } else {
+        TIER_OTHER
+    }
+}
+
+pub struct Openssl {
+    confidence: Confidence,
+}
+impl Openssl {
+    pub fn new(sample_lines: &Lines) -> Openssl {
+        Openssl {
+            confidence: if sample_lines.lines.iter().any(|l| is_header(&l.text)) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Openssl {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let mut in_pem = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // the base64 between the BEGIN and END markers
+            let tier = if line.text.starts_with("-----BEGIN ") {
+                in_pem = true;
+                TIER_OTHER
+            } else if line.text.starts_with("-----END ") {
+                in_pem = false;
+                TIER_OTHER
+            } else if in_pem {
+                TIER_BLOB
+            } else {
+                tier(&line.text)
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const S_CLIENT: &str = "CONNECTED(00000003)
+verify return:1
+Certificate chain
+ 0 s:CN = example.com
+   i:C = US, O = DigiCert Inc
+   v:NotBefore: Jan 30 00:00:00 2026 GMT; NotAfter: Mar  1 23:59:59 2027 GMT
+-----BEGIN CERTIFICATE-----
+MIIHbjCCBlagAwIBAgIQB1vO8waJyK3fE+Ua9K/hhzANBgkqhkiG9w0BAQsFADBZ
+-----END CERTIFICATE-----
+New, TLSv1.3, Cipher is TLS_AES_256_GCM_SHA384
+    0000 - 1f 8b 08 00 00 00 00 00-00 03 ed bd 07 60 1c 49   .............`.I
+Verification: OK
+";
+
+    #[gtest]
+    fn openssl_s_client() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(S_CLIENT), 100, 20)?;
+        let p = Openssl::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_OTHER,
+                TIER_OTHER,
+                TIER_OTHER,
+                TIER_IDENTITY,
+                TIER_IDENTITY,
+                TIER_IDENTITY,
+                TIER_OTHER,
+                TIER_BLOB,
+                TIER_OTHER,
+                TIER_PROTOCOL,
+                TIER_BLOB,
+                TIER_VERIFY,
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn openssl_x509_text() -> Result<()> {
+        let c = Cursor::new(
+            "Certificate:\n    Data:\n        Issuer: CN = Example CA\n            Not After : Mar  1 23:59:59 2027 GMT\n                00:b5:3c:7e:11:a0:9d:\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Openssl::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_IDENTITY, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_IDENTITY, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_BLOB, 4]));
+        Ok(())
+    }
+}
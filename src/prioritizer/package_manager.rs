@@ -0,0 +1,187 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_WARNING: u32 = 1;
+const TIER_SUMMARY: u32 = 2;
+const TIER_LISTING: u32 = 3;
+const TIER_PROGRESS: u32 = 4;
+
+// lines only apt, dnf or pacman print
+const SIGNATURES: [&str; 7] = [
+    "Reading package lists...",
+    "Building dependency tree",
+    "Dependencies resolved.",
+    "Transaction Summary",
+    "resolving dependencies...",
+    ":: Synchronizing package databases...",
+    ":: Proceed with installation?",
+];
+
+fn is_error(text: &str) -> bool {
+    ["E: ", "Error: ", "error: ", "Problem: "]
+        .iter()
+        .any(|p| text.starts_with(p))
+        || text.contains("conflict")
+}
+
+fn is_warning(text: &str) -> bool {
+    text.starts_with("W: ") || text.starts_with("warning: ")
+}
+
+fn is_summary(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    // apt's `3 upgraded, 1 newly installed, 0 to remove and 2 not upgraded.`
+    text.contains(" upgraded, ")
+        || text.starts_with("Need to get ")
+        || text.starts_with("Fetched ")
+        || text.starts_with("Complete!")
+        || text.starts_with("Total download size")
+        || text.starts_with("Total Installed Size")
+        || text.starts_with("Net Upgrade Size")
+        || text.starts_with("Packages (")
+        || text.starts_with("Nothing to do.")
+        // dnf's transaction summary: `Install  3 Packages`
+        || (words.len() == 3 && words[2].starts_with("Package"))
+}
+
+// one line per package and step, hundreds of them
+fn is_progress(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    [
+        "Get:",
+        "Hit:",
+        "Ign:",
+        "Preparing to unpack",
+        "Unpacking ",
+        "Setting up ",
+        "Selecting previously",
+        "Processing triggers",
+        "(",
+        "Installing ",
+        "Upgrading ",
+        "Verifying ",
+        "Cleanup ",
+        "Running scriptlet",
+        "downloading",
+        "checking ",
+        "loading ",
+        "installing ",
+        "upgrading ",
+    ]
+    .iter()
+    .any(|p| trimmed.starts_with(p))
+        || trimmed.contains("% Complete")
+        || trimmed.ends_with("100%")
+}
+
+// `The following packages have been kept back:` and what follows
+fn is_problem_list(text: &str) -> bool {
+    text.starts_with("The following ") && (text.contains("kept back") || text.contains("held"))
+}
+
+pub struct PackageManager {
+    confidence: Confidence,
+}
+impl PackageManager {
+    pub fn new(sample_lines: &Lines) -> PackageManager {
+        PackageManager {
+            confidence: if sample_lines
+                .lines
+                .iter()
+                .any(|l| SIGNATURES.iter().any(|s| l.text.starts_with(s)))
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for PackageManager {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let mut in_problem_list = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            // package lists are indented below their heading
+            in_problem_list = is_problem_list(text) || (in_problem_list && text.starts_with(' '));
+            let tier = if is_error(text) {
+                TIER_ERROR
+            } else if is_warning(text) || in_problem_list {
+                TIER_WARNING
+            } else if is_summary(text) {
+                TIER_SUMMARY
+            } else if is_progress(text) {
+                TIER_PROGRESS
+            } else {
+                TIER_LISTING
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const APT: &str = "Reading package lists... Done
+The following packages have been kept back:
+  linux-generic linux-headers-generic
+The following packages will be upgraded:
+  curl libcurl4
+2 upgraded, 0 newly installed, 0 to remove and 2 not upgraded.
+Get:1 http://archive.ubuntu.com/ubuntu jammy-updates/main amd64 curl 7.81.0 [194 kB]
+Unpacking curl (7.81.0-1ubuntu1.16) over (7.81.0-1ubuntu1.15) ...
+E: Sub-process /usr/bin/dpkg returned an error code (1)
+";
+
+    #[gtest]
+    fn package_manager_apt() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(APT), 100, 20)?;
+        let p = PackageManager::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_LISTING, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_WARNING, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_WARNING, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_LISTING, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_LISTING, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_SUMMARY, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_PROGRESS, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_PROGRESS, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_ERROR, 8]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn package_manager_dnf_and_pacman() -> Result<()> {
+        let c = Cursor::new(
+            "Transaction Summary\nInstall  3 Packages\n(1/3): foo-1.0.rpm  100 kB/s\nComplete!\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        PackageManager::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_SUMMARY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PROGRESS, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_SUMMARY, 3]));
+
+        let c = Cursor::new(
+            "resolving dependencies...\nerror: failed to commit transaction (conflicting files)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        PackageManager::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ERROR, 1]));
+        Ok(())
+    }
+}
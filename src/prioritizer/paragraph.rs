@@ -0,0 +1,102 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const MIN_PARAGRAPHS: usize = 3;
+
+fn is_blank(text: &str) -> bool {
+    text.trim().is_empty()
+}
+
+// position of every line in its paragraph, `None` for the blank lines between them
+fn positions(lines: &Lines) -> Vec<Option<u32>> {
+    let mut position = 0;
+    lines
+        .lines
+        .iter()
+        .map(|l| {
+            if is_blank(&l.text) {
+                position = 0;
+                None
+            } else {
+                position += 1;
+                Some(position - 1)
+            }
+        })
+        .collect()
+}
+
+pub struct Paragraph {
+    confidence: Confidence,
+}
+impl Paragraph {
+    pub fn new(sample_lines: &Lines) -> Paragraph {
+        let positions = positions(sample_lines);
+        let n_paragraphs = positions.iter().filter(|p| **p == Some(0)).count();
+        let n_inner_lines = positions
+            .iter()
+            .filter(|p| p.is_some_and(|p| p > 0))
+            .count();
+        Paragraph {
+            // prose, rather than a list with the odd blank line
+            confidence: if n_paragraphs >= MIN_PARAGRAPHS && n_inner_lines >= n_paragraphs {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Paragraph {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let positions = positions(lines);
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // first lines, then the blank lines separating paragraphs, then the rest
+            line.prio.push(match positions[line_number] {
+                Some(0) => 0,
+                None => 1,
+                Some(p) => p + 1,
+            });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PROSE: &str = "Release 1.2 adds record mode.
+It groups multi-line entries.
+
+Colors are now detected.
+Red lines are kept first.
+Yellow ones come next.
+
+Thanks to all contributors.
+";
+
+    #[gtest]
+    fn paragraph_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PROSE), 100, 20)?;
+        let p = Paragraph::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(20));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![2, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![1, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, 3]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![3, 5]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![0, 7]));
+        Ok(())
+    }
+}
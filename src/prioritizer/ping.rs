@@ -0,0 +1,111 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_STATISTICS: u32 = 0;
+const TIER_LOSS: u32 = 1;
+const TIER_HEADER: u32 = 2;
+const TIER_REPLY: u32 = 3;
+
+// `64 bytes from 93.184.216.34: icmp_seq=1 ttl=56 time=11.2 ms`
+fn is_reply(text: &str) -> bool {
+    text.contains(" bytes from ") && !text.contains("DUP!")
+}
+
+fn sequence(text: &str) -> Option<u32> {
+    let (_, rest) = text.split_once("icmp_seq=").or(text.split_once("seq="))?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+pub struct Ping {
+    confidence: Confidence,
+}
+impl Ping {
+    pub fn new(sample_lines: &Lines) -> Ping {
+        let header = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| l.text.starts_with("PING "));
+        Ping {
+            confidence: if header || share_of_lines(sample_lines, is_reply) >= 0.5 {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Ping {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let len: usize = lines.lines.len();
+        let mut in_statistics = false;
+        let mut last_sequence: Option<u32> = None;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            in_statistics =
+                in_statistics || (text.starts_with("--- ") && text.contains("statistics"));
+            let tier = if in_statistics {
+                TIER_STATISTICS
+            } else if is_reply(text) {
+                // a reply after a hole in the sequence numbers tells when loss ended
+                let sequence = sequence(text);
+                let after_gap =
+                    matches!((last_sequence, sequence), (Some(l), Some(s)) if s > l + 1);
+                last_sequence = sequence.or(last_sequence);
+                if after_gap { TIER_LOSS } else { TIER_REPLY }
+            } else if text.starts_with("PING ") {
+                TIER_HEADER
+            } else if text.trim().is_empty() {
+                TIER_REPLY
+            } else {
+                // timeouts, `Destination Host Unreachable`, `DUP!`
+                TIER_LOSS
+            };
+            line.prio.push(tier);
+            line.prio
+                .push(line_number.min(len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PING: &str = "PING example.com (93.184.216.34) 56(84) bytes of data.
+64 bytes from 93.184.216.34: icmp_seq=1 ttl=56 time=11.2 ms
+64 bytes from 93.184.216.34: icmp_seq=2 ttl=56 time=11.4 ms
+From 10.0.0.1 icmp_seq=3 Destination Host Unreachable
+64 bytes from 93.184.216.34: icmp_seq=5 ttl=56 time=11.3 ms
+--- example.com ping statistics ---
+5 packets transmitted, 3 received, +1 errors, 40% packet loss, time 4005ms
+";
+
+    #[gtest]
+    fn ping_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PING), 100, 20)?;
+        let p = Ping::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_REPLY, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_REPLY, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_LOSS, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_LOSS, 2]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_STATISTICS, 1]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_STATISTICS, 0]));
+        Ok(())
+    }
+}
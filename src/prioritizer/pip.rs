@@ -0,0 +1,176 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_ERROR_OUTPUT: u32 = 1;
+const TIER_SUMMARY: u32 = 2;
+const TIER_WARNING: u32 = 3;
+const TIER_OTHER: u32 = 4;
+const TIER_PROGRESS: u32 = 5;
+
+const SIGNATURES: [&str; 6] = [
+    "Collecting ",
+    "Requirement already satisfied",
+    "Successfully installed ",
+    "Installing collected packages",
+    "Package operations:",
+    "Resolving dependencies...",
+];
+
+fn is_error(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("ERROR:")
+        || trimmed.starts_with("error:")
+        || trimmed.starts_with('×')
+        || trimmed.starts_with("╰─>")
+        // poetry's resolver explanation
+        || trimmed.starts_with("Because ")
+        || trimmed.contains("version solving failed")
+        || trimmed.contains("conflicting dependencies")
+        || trimmed.ends_with("... error")
+}
+
+fn is_summary(text: &str) -> bool {
+    [
+        "Successfully installed ",
+        "Successfully built ",
+        "Installing collected packages",
+        "Package operations:",
+        "Writing lock file",
+        "No dependencies to install",
+        "Installing the current project",
+    ]
+    .iter()
+    .any(|s| text.starts_with(s))
+}
+
+fn is_warning(text: &str) -> bool {
+    text.starts_with("WARNING:") || text.starts_with("DEPRECATION:") || text.starts_with("Warning:")
+}
+
+// one line (or three) per package
+fn is_progress(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    [
+        "Collecting ",
+        "Downloading ",
+        "Using cached ",
+        "Requirement already satisfied",
+        "Obtaining ",
+        "Preparing metadata",
+        "Installing build dependencies",
+        "Getting requirements",
+        "Building wheel",
+        "Created wheel",
+        "Stored in directory",
+        "━",
+        "• ",
+        "- Installing ",
+        "- Updating ",
+    ]
+    .iter()
+    .any(|p| trimmed.starts_with(p))
+}
+
+pub struct Pip {
+    confidence: Confidence,
+}
+impl Pip {
+    pub fn new(sample_lines: &Lines) -> Pip {
+        Pip {
+            confidence: if sample_lines
+                .lines
+                .iter()
+                .any(|l| SIGNATURES.iter().any(|s| l.text.starts_with(s)))
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Pip {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // `╰─> [12 lines of output]` ... `[end of output]` of failed builds
+        let mut in_build_output = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            let tier = if is_error(text) {
+                in_build_output = text.contains("lines of output]");
+                TIER_ERROR
+            } else if in_build_output {
+                in_build_output = !text.contains("[end of output]");
+                TIER_ERROR_OUTPUT
+            } else if is_summary(text) {
+                TIER_SUMMARY
+            } else if is_warning(text) {
+                TIER_WARNING
+            } else if is_progress(text) {
+                TIER_PROGRESS
+            } else {
+                TIER_OTHER
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PIP: &str = "Collecting requests
+  Downloading requests-2.31.0-py3-none-any.whl (62 kB)
+Building wheels for collected packages: foo
+  Building wheel for foo (setup.py) ... error
+  ╰─> [2 lines of output]
+      gcc: error: missing.c: No such file or directory
+      [end of output]
+WARNING: You are using pip version 23.0
+Successfully installed requests-2.31.0
+";
+
+    #[gtest]
+    fn pip_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PIP), 100, 20)?;
+        let p = Pip::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_PROGRESS, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_PROGRESS, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PROGRESS, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ERROR, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ERROR, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ERROR_OUTPUT, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_ERROR_OUTPUT, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_WARNING, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_SUMMARY, 8]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn pip_poetry() -> Result<()> {
+        let c = Cursor::new(
+            "Resolving dependencies...\nBecause a depends on c (^2) and b depends on c (^1), a is incompatible with b.\nPackage operations: 2 installs, 0 updates, 0 removals\n  • Installing idna (3.4)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        Pip::new(&lines).prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_OTHER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ERROR, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_SUMMARY, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_PROGRESS, 3]));
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use super::score::order_key;
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+// never detected, only used when asked for with `--prio-field`, like
+// `sort -k`. lines too short to have the field go last
+pub struct PrioField {
+    confidence: Confidence,
+    field: usize,
+    delimiter: Option<String>,
+    numeric: bool,
+    reverse: bool,
+}
+impl PrioField {
+    // `field` counts from 1
+    pub fn new(field: usize, delimiter: Option<String>, numeric: bool, reverse: bool) -> PrioField {
+        PrioField {
+            confidence: Confidence::Certain,
+            field,
+            delimiter,
+            numeric,
+            reverse,
+        }
+    }
+
+    fn value<'a>(&self, text: &'a str) -> Option<&'a str> {
+        let index = self.field.checked_sub(1)?;
+        match &self.delimiter {
+            Some(delimiter) => text.split(delimiter.as_str()).nth(index).map(str::trim),
+            None => text.split_whitespace().nth(index),
+        }
+    }
+}
+impl Prioritizer for PrioField {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // text fields are ranked among all the values present
+        let mut values: Vec<String> = lines
+            .lines
+            .iter()
+            .filter_map(|l| self.value(&l.text))
+            .map(|v| v.to_string())
+            .collect();
+        values.sort();
+        values.dedup();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let value = self.value(&line.text);
+            let key = match value {
+                None => u32::MAX,
+                Some(v) if self.numeric => match v.replace(',', "").parse::<f64>() {
+                    Ok(n) if self.reverse => order_key(-n),
+                    Ok(n) => order_key(n),
+                    Err(_) => u32::MAX,
+                },
+                Some(v) => {
+                    let rank = values.binary_search_by(|x| x.as_str().cmp(v)).unwrap_or(0);
+                    if self.reverse {
+                        (values.len() - 1 - rank) as u32
+                    } else {
+                        rank as u32
+                    }
+                }
+            };
+            line.prio.push(key);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TABLE: &str = "web 3 running
+db 120 running
+cache 45 stopped
+";
+
+    fn keys(lines: &Lines) -> Vec<u32> {
+        lines.lines.iter().map(|l| l.prio[0]).collect()
+    }
+
+    #[gtest]
+    fn prio_field_numeric() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TABLE), 100, 20)?;
+        PrioField::new(2, None, true, true).prioritize(&mut lines)?;
+        expect_that!(lines.lines[1].prio, lt(&lines.lines[2].prio));
+        expect_that!(lines.lines[2].prio, lt(&lines.lines[0].prio));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prio_field_text() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TABLE), 100, 20)?;
+        // as text, `120` sorts before `3` and `45`
+        PrioField::new(2, None, false, false).prioritize(&mut lines)?;
+        expect_that!(keys(&lines), eq(&vec![1, 0, 2]));
+
+        let c = Cursor::new("a,zeta,1\nb,alpha,2\nc\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        PrioField::new(2, Some(",".to_string()), false, true).prioritize(&mut lines)?;
+        expect_that!(keys(&lines), eq(&vec![0, 1, u32::MAX]));
+        Ok(())
+    }
+}
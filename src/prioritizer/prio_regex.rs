@@ -0,0 +1,82 @@
+use super::score::order_key;
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::{Context, Result};
+use regex::Regex;
+
+// never detected, only used when asked for with `--prio-regex`. the first
+// capture group (or the whole match) is read as a number, lines without one
+// go last
+pub struct PrioRegex {
+    confidence: Confidence,
+    pattern: Regex,
+    reverse: bool,
+}
+impl PrioRegex {
+    pub fn new(pattern: &str, reverse: bool) -> Result<PrioRegex> {
+        Ok(PrioRegex {
+            confidence: Confidence::Certain,
+            pattern: Regex::new(pattern)
+                .with_context(|| format!("invalid pattern: {}", pattern))?,
+            reverse,
+        })
+    }
+
+    fn value(&self, text: &str) -> Option<f64> {
+        let captures = self.pattern.captures(text)?;
+        let number = captures.get(1).or(captures.get(0))?.as_str();
+        number.replace(',', "").parse().ok()
+    }
+}
+impl Prioritizer for PrioRegex {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let key = match self.value(&line.text) {
+                Some(value) if self.reverse => order_key(-value),
+                Some(value) => order_key(value),
+                None => u32::MAX,
+            };
+            line.prio.push(key);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const LOG: &str = "GET / took 12ms
+GET /slow took 1,250ms
+starting
+GET /api took 80ms
+";
+
+    #[gtest]
+    fn prio_regex_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(LOG), 100, 20)?;
+        PrioRegex::new(r"took ([\d,]+)ms", true)?.prioritize(&mut lines)?;
+        expect_that!(lines.lines[1].prio, lt(&lines.lines[3].prio));
+        expect_that!(lines.lines[3].prio, lt(&lines.lines[0].prio));
+        expect_that!(lines.lines[2].prio[0], eq(u32::MAX));
+
+        let mut lines = Lines::from_reader(Cursor::new(LOG), 100, 20)?;
+        PrioRegex::new(r"\d+", false)?.prioritize(&mut lines)?;
+        expect_that!(lines.lines[0].prio, lt(&lines.lines[3].prio));
+        Ok(())
+    }
+
+    #[gtest]
+    fn prio_regex_invalid_pattern() {
+        expect_that!(PrioRegex::new("took (", false).is_err(), eq(true));
+    }
+}
@@ -0,0 +1,102 @@
+use super::units::parse_number;
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_PROCESS: u32 = 1;
+
+fn is_header(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.contains(&"PID") && (words.contains(&"%CPU") || words.contains(&"%MEM"))
+}
+
+pub struct Ps {
+    confidence: Confidence,
+}
+impl Ps {
+    pub fn new(sample_lines: &Lines) -> Ps {
+        Ps {
+            confidence: if sample_lines.lines.iter().any(|l| is_header(&l.text)) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Ps {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // top prints its summary above the header, it's kept along with it
+        let header = lines.lines.iter().position(|l| is_header(&l.text));
+        let columns: Vec<usize> = header
+            .map(|h| {
+                lines.lines[h]
+                    .text
+                    .split_whitespace()
+                    .enumerate()
+                    .filter(|(_, w)| *w == "%CPU" || *w == "%MEM")
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let load: Vec<f64> = lines
+            .lines
+            .iter()
+            .map(|l| {
+                let words: Vec<&str> = l.text.split_whitespace().collect();
+                columns
+                    .iter()
+                    .filter_map(|c| parse_number(words.get(*c)?))
+                    .sum()
+            })
+            .collect();
+        let mut sorted: Vec<f64> = load.clone();
+        sorted.sort_by(|a, b| b.total_cmp(a));
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            if header.is_none_or(|h| line_number <= h) {
+                line.prio.push(TIER_HEADER);
+                line.prio.push(line_number as u32);
+            } else {
+                // number of processes heavier than this one
+                let rank = sorted.partition_point(|l| *l > load[line_number]);
+                line.prio.push(TIER_PROCESS);
+                line.prio.push(rank as u32);
+            }
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PS: &str = "USER  PID %CPU %MEM COMMAND
+root    1  0.0  0.1 /sbin/init
+me    420 95.0  2.0 cargo build --release
+me    421  0.5 30.0 firefox -P default
+me    422  0.0  0.0 sleep 10
+";
+
+    #[gtest]
+    fn ps_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PS), 100, 20)?;
+        let p = Ps::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_PROCESS, 2]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_PROCESS, 0]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_PROCESS, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_PROCESS, 3]));
+        Ok(())
+    }
+}
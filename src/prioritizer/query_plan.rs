@@ -0,0 +1,149 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_SEQ_SCAN: u32 = 1;
+const TIER_EXPENSIVE: u32 = 2;
+const TIER_NODE: u32 = 3;
+const TIER_DETAIL: u32 = 4;
+
+// a node is expensive when its cost or row estimate is within this factor of the largest
+const EXPENSIVE_FACTOR: f64 = 0.1;
+
+// postgres' `Seq Scan on`, mysql's `Table scan on`
+const FULL_SCANS: [&str; 3] = ["Seq Scan on", "Table scan on", "Full scan on"];
+
+struct Node {
+    cost: f64,
+    rows: f64,
+}
+
+// the value after `name=`, for ranges such as `cost=0.00..1.04` the upper bound
+fn estimate(text: &str, name: &str) -> Option<f64> {
+    let (_, rest) = text.split_once(name)?;
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == 'e' || c == '+'))
+        .unwrap_or(rest.len());
+    let value = &rest[..end];
+    value.rsplit("..").next()?.parse().ok()
+}
+
+// `   ->  Seq Scan on a  (cost=0.00..1.04 rows=4 width=36)`, the top node has no arrow
+fn node(text: &str) -> Option<Node> {
+    if !text.trim_start().starts_with("->") && !text.contains("(cost=") {
+        return None;
+    }
+    Some(Node {
+        cost: estimate(text, "cost=").unwrap_or(0.0),
+        rows: estimate(text, "rows=").unwrap_or(0.0),
+    })
+}
+
+fn is_header(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed == "QUERY PLAN"
+        || trimmed == "EXPLAIN"
+        || (!trimmed.is_empty() && trimmed.chars().all(|c| c == '-' || c == '+'))
+        || trimmed.starts_with("Planning Time")
+        || trimmed.starts_with("Execution Time")
+        || (trimmed.starts_with('(') && trimmed.ends_with(" rows)"))
+}
+
+fn depth(text: &str) -> u32 {
+    text.chars().take_while(|c| *c == ' ').count() as u32
+}
+
+pub struct QueryPlan {
+    confidence: Confidence,
+}
+impl QueryPlan {
+    pub fn new(sample_lines: &Lines) -> QueryPlan {
+        let header = sample_lines
+            .lines
+            .iter()
+            .any(|l| l.text.trim() == "QUERY PLAN");
+        let n_costs = sample_lines
+            .lines
+            .iter()
+            .filter(|l| l.text.contains("(cost="))
+            .count();
+        QueryPlan {
+            confidence: if header || n_costs >= 2 {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for QueryPlan {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let nodes: Vec<Option<Node>> = lines.lines.iter().map(|l| node(&l.text)).collect();
+        let max_cost = nodes.iter().flatten().map(|n| n.cost).fold(0.0, f64::max);
+        let max_rows = nodes.iter().flatten().map(|n| n.rows).fold(0.0, f64::max);
+        let top_node = nodes.iter().position(|n| n.is_some());
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match &nodes[line_number] {
+                _ if is_header(&line.text) => TIER_HEADER,
+                Some(_) if Some(line_number) == top_node => TIER_HEADER,
+                Some(_) if FULL_SCANS.iter().any(|s| line.text.contains(s)) => TIER_SEQ_SCAN,
+                Some(n)
+                    if (max_cost > 0.0 && n.cost >= max_cost * EXPENSIVE_FACTOR)
+                        || (max_rows > 0.0 && n.rows >= max_rows * EXPENSIVE_FACTOR) =>
+                {
+                    TIER_EXPENSIVE
+                }
+                Some(_) => TIER_NODE,
+                // `Hash Cond:`, `Filter:`, `Buffers:` ...
+                None => TIER_DETAIL,
+            };
+            line.prio.push(tier);
+            line.prio.push(depth(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const EXPLAIN: &str = "                          QUERY PLAN
+---------------------------------------------------------------
+ Hash Join  (cost=1.09..2000.22 rows=40000 width=64)
+   Hash Cond: (a.id = b.id)
+   ->  Seq Scan on a  (cost=0.00..1.04 rows=4 width=36)
+   ->  Hash  (cost=1.04..1.04 rows=4 width=36)
+         ->  Index Scan using b_pkey on b  (cost=0.29..900.00 rows=20 width=36)
+ Planning Time: 0.1 ms
+(7 rows)
+";
+
+    #[gtest]
+    fn query_plan_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(EXPLAIN), 100, 20)?;
+        let p = QueryPlan::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 26, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_HEADER, 0, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_HEADER, 1, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_DETAIL, 3, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_SEQ_SCAN, 3, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_NODE, 3, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_EXPENSIVE, 9, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_HEADER, 1, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_HEADER, 0, 8]));
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use super::cluster::{template, tokens};
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+
+// below this, head and tail already show most of the input
+const MIN_LINES: usize = 20;
+// at most this share of the lines can be one of a kind for the rest to be
+// boilerplate the rare ones stand out from
+const MAX_SINGLETON_SHARE: f64 = 0.2;
+// lines are ranked in this many bands of rarity, then by distance to the ends
+const N_BANDS: f64 = 10.0;
+
+// mean inverse document frequency of the distinct words of every line: words
+// found on every line score 0, a word seen once scores ln(number of lines)
+fn rarity(lines: &Lines) -> Vec<f64> {
+    let words: Vec<HashSet<String>> = lines
+        .lines
+        .iter()
+        .map(|l| tokens(&l.text).into_iter().collect())
+        .collect();
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for word in words.iter().flatten() {
+        *document_frequency.entry(word).or_insert(0) += 1;
+    }
+    let n_lines = lines.lines.len() as f64;
+    words
+        .iter()
+        .map(|line_words| {
+            if line_words.is_empty() {
+                return 0.0;
+            }
+            let total: f64 = line_words
+                .iter()
+                .map(|w| (n_lines / document_frequency[w.as_str()] as f64).ln())
+                .sum();
+            total / line_words.len() as f64
+        })
+        .collect()
+}
+
+pub struct Rarity {
+    confidence: Confidence,
+}
+impl Rarity {
+    // worth it when most lines repeat a few templates, a handful of others
+    // being the only ones of their kind
+    pub fn new(sample_lines: &Lines) -> Rarity {
+        let n_lines = sample_lines.lines.len();
+        let mut counts: HashMap<Vec<String>, usize> = HashMap::new();
+        for line in &sample_lines.lines {
+            *counts.entry(template(&line.text)).or_insert(0) += 1;
+        }
+        let n_singletons = counts.values().filter(|c| **c == 1).count();
+        Rarity {
+            confidence: if n_lines >= MIN_LINES
+                && n_singletons > 0
+                && n_singletons as f64 <= MAX_SINGLETON_SHARE * n_lines as f64
+            {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Rarity {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let rarity = rarity(lines);
+        let max_rarity = rarity.iter().cloned().fold(0.0, f64::max);
+        let len: usize = lines.lines.len();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let band = if max_rarity > 0.0 {
+                ((max_rarity - rarity[line_number]) / max_rarity * N_BANDS) as u32
+            } else {
+                0
+            };
+            line.prio.push(band);
+            line.prio
+                .push(line_number.min(len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn rarity_prioritizer() -> Result<()> {
+        let mut input = String::new();
+        for i in 0..30 {
+            if i == 12 {
+                input += "worker 12 lost its lease, resyncing\n";
+            } else {
+                input += &format!("worker {} heartbeat ok\n", i);
+            }
+        }
+        let mut lines = Lines::from_reader(Cursor::new(input), 100, 10)?;
+        let p = Rarity::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(20));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[12].prio, eq(&vec![0, 12]));
+        expect_that!(lines.lines[0].prio[0], gt(5));
+        expect_that!(lines.lines[29].prio[0], gt(5));
+        Ok(())
+    }
+
+    #[gtest]
+    fn rarity_ordinary_input() -> Result<()> {
+        let input: String = (b'a'..=b'z')
+            .map(|c| format!("src/{}.rs: {} bytes\n", c as char, c as usize * 10))
+            .collect();
+        let lines = Lines::from_reader(Cursor::new(input), 100, 10)?;
+        expect_that!(u32::from(Rarity::new(&lines).confidence()), eq(10));
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use super::log_level::{SEVERITY_WARN, severity};
+use super::timestamp::leading_timestamp;
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+pub struct Recency {
+    confidence: Confidence,
+}
+impl Recency {
+    pub fn new(sample_lines: &Lines) -> Recency {
+        let share = share_of_lines(sample_lines, |t| leading_timestamp(t).is_some());
+        Recency {
+            confidence: if sample_lines.lines.len() > 2 && share >= 0.5 {
+                Confidence::High
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Recency {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // lines without a timestamp continue the previous line
+        let mut last_timestamp = 0;
+        let timestamps: Vec<u64> = lines
+            .lines
+            .iter()
+            .map(|l| {
+                last_timestamp = leading_timestamp(&l.text).unwrap_or(last_timestamp);
+                last_timestamp
+            })
+            .collect();
+        let newest = timestamps.iter().copied().max().unwrap_or(0);
+        let len: usize = lines.lines.len();
+        let mut anomaly = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // old errors and warnings are kept along with the recent lines
+            if leading_timestamp(&line.text).is_some() {
+                anomaly = severity(&line.text).is_some_and(|s| s <= SEVERITY_WARN);
+            }
+            let age = (newest - timestamps[line_number]).min(u32::MAX as u64) as u32;
+            line.prio.push(if anomaly { 0 } else { 1 });
+            line.prio.push(age);
+            line.prio.push((len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn recency_prioritizer() -> Result<()> {
+        let c = Cursor::new(
+            "2025-06-01 10:00:00 INFO old\n\
+             2025-06-01 10:00:05 ERROR old but bad\n\
+             \tcontinuation\n\
+             2025-06-01 10:00:09 INFO recent\n\
+             2025-06-01 10:00:10 INFO newest\n",
+        );
+        let mut lines = Lines::from_reader(c, 40, 20)?;
+        let p = Recency::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(30));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![1, 10, 4]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 5, 3]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![0, 5, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![1, 1, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![1, 0, 0]));
+        Ok(())
+    }
+}
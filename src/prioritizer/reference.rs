@@ -0,0 +1,92 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::{Context, Result};
+use regex::Regex;
+
+// `src/main.rs:12`, `main.c:3:5` and urls
+const DEFAULT_PATTERNS: [&str; 2] = [r"[\w./-]*\w\.\w+:\d+", r"https?://\S+"];
+
+// references stand out when only some lines have them
+const MIN_SHARE: f32 = 0.05;
+const MAX_SHARE: f32 = 0.7;
+
+pub struct References {
+    confidence: Confidence,
+    patterns: Vec<Regex>,
+}
+impl References {
+    pub fn new(sample_lines: &Lines, extra_patterns: &[String]) -> Result<References> {
+        let patterns = DEFAULT_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .chain(extra_patterns.iter().cloned())
+            .map(|p| Regex::new(&p).with_context(|| format!("invalid pattern: {}", p)))
+            .collect::<Result<Vec<Regex>>>()?;
+        let share = share_of_lines(sample_lines, |t| patterns.iter().any(|p| p.is_match(t)));
+        Ok(References {
+            confidence: if (MIN_SHARE..=MAX_SHARE).contains(&share) {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+            patterns,
+        })
+    }
+}
+impl Prioritizer for References {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let is_reference = self.patterns.iter().any(|p| p.is_match(&line.text));
+            line.prio.push(if is_reference { 0 } else { 1 });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const OUTPUT: &str = "building
+see src/lines.rs:42 for details
+still building
+docs at https://example.com/oneless
+ticket ABC-123 reopened
+done
+";
+
+    #[gtest]
+    fn references_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(OUTPUT), 100, 20)?;
+        let p = References::new(&lines, &[])?;
+        expect_that!(u32::from(p.confidence()), eq(20));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![1, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![1, 4]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn references_extra_patterns() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(OUTPUT), 100, 20)?;
+        let p = References::new(&lines, &[String::from(r"[A-Z]+-\d+")])?;
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[4].prio, eq(&vec![0, 4]));
+        expect_that!(
+            References::new(&lines, &[String::from("(")]).is_err(),
+            eq(true)
+        );
+        Ok(())
+    }
+}
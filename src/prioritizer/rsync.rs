@@ -0,0 +1,132 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_ERROR: u32 = 0;
+const TIER_SUMMARY: u32 = 1;
+const TIER_DELETION: u32 = 2;
+const TIER_FILE: u32 = 3;
+const TIER_PROGRESS: u32 = 4;
+
+const SUMMARY_PREFIXES: [&str; 7] = [
+    "sent ",
+    "total size is ",
+    "Number of ",
+    "Total ",
+    "Literal data",
+    "Matched data",
+    "File list ",
+];
+
+fn is_header(text: &str) -> bool {
+    text.starts_with("sending incremental file list")
+        || text.starts_with("receiving incremental file list")
+        || text.starts_with("building file list")
+        || text.starts_with("receiving file list")
+}
+
+fn is_error(text: &str) -> bool {
+    text.starts_with("rsync:")
+        || text.starts_with("rsync error")
+        || text.starts_with("scp:")
+        || text.starts_with("ssh:")
+        || text.contains("Permission denied")
+        || text.contains("No such file or directory")
+}
+
+// rsync's `      1,234 100%    1.23MB/s    0:00:00 (xfr#1, to-chk=5/7)` and
+// scp's `file.txt    100%  1234   1.2MB/s   00:00`
+fn is_progress(text: &str) -> bool {
+    text.split_whitespace().any(|w| {
+        w.strip_suffix('%')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    }) && text.contains("/s")
+}
+
+fn tier(text: &str) -> u32 {
+    if is_error(text) {
+        TIER_ERROR
+    } else if is_header(text) || SUMMARY_PREFIXES.iter().any(|p| text.starts_with(p)) {
+        TIER_SUMMARY
+    } else if text.starts_with("deleting ") || text.starts_with("*deleting ") {
+        TIER_DELETION
+    } else if is_progress(text) || text.trim().is_empty() {
+        TIER_PROGRESS
+    } else {
+        TIER_FILE
+    }
+}
+
+pub struct Rsync {
+    confidence: Confidence,
+}
+impl Rsync {
+    pub fn new(sample_lines: &Lines) -> Rsync {
+        let header = sample_lines.lines.iter().any(|l| is_header(&l.text));
+        Rsync {
+            confidence: if header || share_of_lines(sample_lines, is_progress) >= 0.5 {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Rsync {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(tier(&line.text));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const RSYNC: &str = "sending incremental file list
+deleting old/file.txt
+dir/a.txt
+      1,234 100%    1.23MB/s    0:00:00 (xfr#1, to-chk=5/7)
+rsync: [sender] send_files failed to open \"/x\": Permission denied (13)
+
+sent 1,234 bytes  received 56 bytes  2,580.00 bytes/sec
+total size is 10,000  speedup is 7.75
+";
+
+    #[gtest]
+    fn rsync_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(RSYNC), 100, 20)?;
+        let p = Rsync::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_SUMMARY, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_DELETION, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FILE, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_PROGRESS, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ERROR, 4]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_SUMMARY, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_SUMMARY, 7]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn rsync_scp() -> Result<()> {
+        let c = Cursor::new(
+            "a.txt    100%  1234   1.2MB/s   00:00\nb.txt    100%  5678   2.2MB/s   00:00\n",
+        );
+        let lines = Lines::from_reader(c, 100, 20)?;
+        expect_that!(u32::from(Rsync::new(&lines).confidence()), eq(100));
+        Ok(())
+    }
+}
@@ -0,0 +1,362 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+
+// a tiny expression language for `--score`, e.g.
+// `indent*10 + (contains("ERROR") ? -100 : 0)`. every line gets a number, the
+// lowest ones are kept first. booleans are 1 and 0
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Variable {
+    Indent,
+    Length,
+    Line,
+    Words,
+}
+
+#[derive(Clone, Debug)]
+enum Function {
+    Contains(String),
+    StartsWith(String),
+    EndsWith(String),
+    Matches(Regex),
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Call(Function),
+    Negate(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(String, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Str(String),
+    Symbol(&'static str),
+}
+
+// longest first, so that `<=` is not read as `<`
+const SYMBOLS: [&str; 17] = [
+    "<=", ">=", "==", "!=", "&&", "||", "<", ">", "+", "-", "*", "/", "!", "?", ":", "(", ")",
+];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let number = rest[..end]
+                .parse()
+                .with_context(|| format!("invalid number: {}", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            tokens.push(Token::Name(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else if c == '"' {
+            let Some(end) = rest[1..].find('"') else {
+                bail!("unterminated string in score: {}", expression);
+            };
+            tokens.push(Token::Str(rest[1..end + 1].to_string()));
+            rest = &rest[end + 2..];
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| rest.starts_with(**s)) {
+            tokens.push(Token::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else {
+            bail!("unexpected {:?} in score: {}", c, expression);
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<()> {
+        if !self.eat(symbol) {
+            bail!("expected {} in score, found {:?}", symbol, self.peek());
+        }
+        Ok(())
+    }
+
+    // expr := or ('?' expr ':' expr)?
+    fn expression(&mut self) -> Result<Expr> {
+        let condition = self.binary(0)?;
+        if self.eat("?") {
+            let then = self.expression()?;
+            self.expect(":")?;
+            let otherwise = self.expression()?;
+            return Ok(Expr::Conditional(
+                Box::new(condition),
+                Box::new(then),
+                Box::new(otherwise),
+            ));
+        }
+        Ok(condition)
+    }
+
+    // binary operators by increasing precedence
+    fn binary(&mut self, level: usize) -> Result<Expr> {
+        const LEVELS: [&[&str]; 5] = [
+            &["||"],
+            &["&&"],
+            &["<=", ">=", "==", "!=", "<", ">"],
+            &["+", "-"],
+            &["*", "/"],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Symbol(symbol)) = self.peek()
+            && LEVELS[level].contains(symbol)
+        {
+            let symbol = symbol.to_string();
+            self.position += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(symbol, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat("-") {
+            Ok(Expr::Negate(Box::new(self.unary()?)))
+        } else if self.eat("!") {
+            Ok(Expr::Not(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Symbol("(")) => {
+                let inner = self.expression()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(Token::Name(name)) if self.eat("(") => {
+                let Some(Token::Str(argument)) = self.next() else {
+                    bail!("{}() takes a string in score", name);
+                };
+                self.expect(")")?;
+                Ok(Expr::Call(match name.as_str() {
+                    "contains" => Function::Contains(argument),
+                    "starts_with" => Function::StartsWith(argument),
+                    "ends_with" => Function::EndsWith(argument),
+                    "matches" => Function::Matches(
+                        Regex::new(&argument)
+                            .with_context(|| format!("invalid pattern: {}", argument))?,
+                    ),
+                    _ => bail!("unknown function in score: {}", name),
+                }))
+            }
+            Some(Token::Name(name)) => Ok(Expr::Variable(match name.as_str() {
+                "indent" => Variable::Indent,
+                "length" => Variable::Length,
+                "line" => Variable::Line,
+                "words" => Variable::Words,
+                _ => bail!(
+                    "unknown variable in score: {} (expected indent, length, line or words)",
+                    name
+                ),
+            })),
+            token => bail!("unexpected {:?} in score", token),
+        }
+    }
+}
+
+fn evaluate(expr: &Expr, text: &str, line_number: usize) -> f64 {
+    let truth = |b: bool| if b { 1.0 } else { 0.0 };
+    match expr {
+        Expr::Number(n) => *n,
+        Expr::Variable(Variable::Indent) => (text.len() - text.trim_start().len()) as f64,
+        Expr::Variable(Variable::Length) => text.chars().count() as f64,
+        Expr::Variable(Variable::Line) => line_number as f64,
+        Expr::Variable(Variable::Words) => text.split_whitespace().count() as f64,
+        Expr::Call(Function::Contains(s)) => truth(text.contains(s.as_str())),
+        Expr::Call(Function::StartsWith(s)) => truth(text.starts_with(s.as_str())),
+        Expr::Call(Function::EndsWith(s)) => truth(text.ends_with(s.as_str())),
+        Expr::Call(Function::Matches(r)) => truth(r.is_match(text)),
+        Expr::Negate(e) => -evaluate(e, text, line_number),
+        Expr::Not(e) => truth(evaluate(e, text, line_number) == 0.0),
+        Expr::Conditional(condition, then, otherwise) => {
+            if evaluate(condition, text, line_number) != 0.0 {
+                evaluate(then, text, line_number)
+            } else {
+                evaluate(otherwise, text, line_number)
+            }
+        }
+        Expr::Binary(op, left, right) => {
+            let a = evaluate(left, text, line_number);
+            let b = evaluate(right, text, line_number);
+            match op.as_str() {
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" => a / b,
+                "<" => truth(a < b),
+                ">" => truth(a > b),
+                "<=" => truth(a <= b),
+                ">=" => truth(a >= b),
+                "==" => truth(a == b),
+                "!=" => truth(a != b),
+                "&&" => truth(a != 0.0 && b != 0.0),
+                _ => truth(a != 0.0 || b != 0.0),
+            }
+        }
+    }
+}
+
+// maps a number to a u32 that sorts the same way, through the bits of the
+// f32 closest to it. NaN sorts last
+pub fn order_key(value: f64) -> u32 {
+    if value.is_nan() {
+        return u32::MAX;
+    }
+    let bits = (value as f32).to_bits();
+    if bits >> 31 == 1 {
+        !bits
+    } else {
+        bits | 1 << 31
+    }
+}
+
+// never detected, only used when asked for with `--score`
+pub struct Score {
+    confidence: Confidence,
+    expression: Expr,
+    reverse: bool,
+}
+impl Score {
+    pub fn new(expression: &str, reverse: bool) -> Result<Score> {
+        let mut parser = Parser {
+            tokens: tokenize(expression)?,
+            position: 0,
+        };
+        let parsed = parser.expression()?;
+        if let Some(token) = parser.peek() {
+            bail!("unexpected {:?} in score: {}", token, expression);
+        }
+        Ok(Score {
+            confidence: Confidence::Certain,
+            expression: parsed,
+            reverse,
+        })
+    }
+}
+impl Prioritizer for Score {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let score = evaluate(&self.expression, &line.text, line_number);
+            let score = if self.reverse { -score } else { score };
+            line.prio.push(order_key(score));
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    fn score(expression: &str, text: &str) -> Result<f64> {
+        Ok(evaluate(
+            &Score::new(expression, false)?.expression,
+            text,
+            7,
+        ))
+    }
+
+    #[gtest]
+    fn score_expressions() -> Result<()> {
+        expect_that!(score("1 + 2 * 3", "")?, eq(7.0));
+        expect_that!(score("(1 + 2) * 3", "")?, eq(9.0));
+        expect_that!(score("-line / 2", "")?, eq(-3.5));
+        expect_that!(score("indent*10", "    x")?, eq(40.0));
+        expect_that!(score("words >= 2 && !starts_with(\"#\")", "a b")?, eq(1.0));
+        expect_that!(
+            score("indent*10 + (contains(\"ERROR\") ? -100 : 0)", "  ERROR x")?,
+            eq(-80.0)
+        );
+        expect_that!(score("matches(\"\\d+ms\") ? 1 : 2", "took 3ms")?, eq(1.0));
+        expect_that!(score("length", "héllo")?, eq(5.0));
+        Ok(())
+    }
+
+    #[gtest]
+    fn score_invalid_expressions() {
+        expect_that!(Score::new("1 +", false).is_err(), eq(true));
+        expect_that!(Score::new("depth", false).is_err(), eq(true));
+        expect_that!(Score::new("contains(ERROR)", false).is_err(), eq(true));
+        expect_that!(Score::new("(1", false).is_err(), eq(true));
+        expect_that!(Score::new("1 2", false).is_err(), eq(true));
+        expect_that!(Score::new("\"x", false).is_err(), eq(true));
+    }
+
+    #[gtest]
+    fn score_order_key() {
+        expect_that!(order_key(-100.0), lt(order_key(-1.0)));
+        expect_that!(order_key(-1.0), lt(order_key(0.0)));
+        expect_that!(order_key(0.0), lt(order_key(0.5)));
+        expect_that!(order_key(0.5), lt(order_key(1e9)));
+        expect_that!(order_key(f64::NAN), eq(u32::MAX));
+    }
+
+    #[gtest]
+    fn score_prioritizer() -> Result<()> {
+        let c = Cursor::new("  nested\nERROR boom\ntop\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        Score::new("indent*10 + (contains(\"ERROR\") ? -100 : 0)", false)?
+            .prioritize(&mut lines)?;
+        expect_that!(lines.lines[1].prio, lt(&lines.lines[2].prio));
+        expect_that!(lines.lines[2].prio, lt(&lines.lines[0].prio));
+        Ok(())
+    }
+}
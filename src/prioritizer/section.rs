@@ -0,0 +1,116 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const MIN_HEADERS: usize = 3;
+// longer lines ending in `:` are sentences introducing what follows
+const MAX_HEADER_LENGTH: usize = 60;
+
+fn is_underline(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.len() >= 3 && (trimmed.chars().all(|c| c == '-') || trimmed.chars().all(|c| c == '='))
+}
+
+// `Options:`, `DESCRIPTION`, or a line followed by `-----`/`=====`
+fn is_header(text: &str, next: Option<&str>) -> bool {
+    let trimmed = text.trim();
+    if trimmed.is_empty() || is_underline(text) {
+        return false;
+    }
+    let n_letters = trimmed.chars().filter(|c| c.is_alphabetic()).count();
+    let all_caps = n_letters >= 2
+        && trimmed
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| c.is_uppercase());
+    next.is_some_and(is_underline)
+        || all_caps
+        || (trimmed.ends_with(':') && trimmed.chars().count() <= MAX_HEADER_LENGTH)
+}
+
+fn headers(lines: &Lines) -> Vec<bool> {
+    let texts: Vec<&str> = lines.lines.iter().map(|l| l.text.as_str()).collect();
+    (0..texts.len())
+        .map(|i| is_header(texts[i], texts.get(i + 1).copied()))
+        .collect()
+}
+
+pub struct Section {
+    confidence: Confidence,
+}
+impl Section {
+    pub fn new(sample_lines: &Lines) -> Section {
+        let headers = headers(sample_lines);
+        let n_headers = headers.iter().filter(|h| **h).count();
+        Section {
+            // a skeleton needs some flesh around it
+            confidence: if n_headers >= MIN_HEADERS && n_headers * 2 <= headers.len() {
+                Confidence::Medium
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Section {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let headers = headers(lines);
+        let mut previous_header = false;
+        let mut index_in_section = 0;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // headers and their underlines, then the first lines of every section
+            let rank = if headers[line_number] || (previous_header && is_underline(&line.text)) {
+                index_in_section = 0;
+                0
+            } else {
+                index_in_section += 1;
+                index_in_section
+            };
+            previous_header = headers[line_number];
+            line.prio.push(rank);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const REPORT: &str = "Summary
+=======
+3 hosts checked
+NETWORK
+eth0 up
+eth1 down
+Disks:
+sda ok
+sdb ok
+";
+
+    #[gtest]
+    fn section_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(REPORT), 100, 20)?;
+        let p = Section::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(20));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![1, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![1, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![2, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![0, 6]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![2, 8]));
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_LISTEN: u32 = 1;
+const TIER_UNUSUAL: u32 = 2;
+const TIER_ESTABLISHED: u32 = 3;
+const TIER_OTHER: u32 = 4;
+const TIER_TIME_WAIT: u32 = 5;
+
+const COMMON_PORTS: [u16; 12] = [22, 25, 53, 80, 110, 143, 443, 465, 587, 993, 995, 8080];
+// peers on ephemeral ports are clients connecting to us
+const EPHEMERAL_PORTS_START: u16 = 32768;
+
+fn is_header(text: &str) -> bool {
+    text.contains("Recv-Q") && text.contains("Send-Q")
+}
+
+// `10.0.0.1:443`, `[::1]:53`, `*:22`, BSD netstat's `10.0.0.1.443`
+fn port(address: &str) -> Option<u16> {
+    let (_, port) = address.rsplit_once(':').or(address.rsplit_once('.'))?;
+    port.parse().ok()
+}
+
+fn tier(text: &str) -> u32 {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let state = words.iter().find(|w| {
+        w.chars()
+            .all(|c| c.is_ascii_uppercase() || c == '_' || c == '-')
+            && w.len() > 3
+    });
+    let addresses: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|w| port(w).is_some() || w.ends_with(":*") || w.ends_with(".*"))
+        .collect();
+    let peer_port = addresses.get(1).and_then(|a| port(a));
+    match state.copied() {
+        Some("LISTEN" | "LISTENING") => TIER_LISTEN,
+        Some("ESTAB" | "ESTABLISHED") => match peer_port {
+            Some(p) if !COMMON_PORTS.contains(&p) && p < EPHEMERAL_PORTS_START => TIER_UNUSUAL,
+            _ => TIER_ESTABLISHED,
+        },
+        Some("TIME-WAIT" | "TIME_WAIT") => TIER_TIME_WAIT,
+        // piling up CLOSE_WAIT or SYN_SENT usually means trouble
+        Some("CLOSE-WAIT" | "CLOSE_WAIT" | "SYN-SENT" | "SYN_SENT") => TIER_UNUSUAL,
+        _ => TIER_OTHER,
+    }
+}
+
+pub struct Sockets {
+    confidence: Confidence,
+}
+impl Sockets {
+    pub fn new(sample_lines: &Lines) -> Sockets {
+        Sockets {
+            confidence: if sample_lines.lines.iter().any(|l| is_header(&l.text)) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Sockets {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // netstat prints a title above the header
+            let tier = if is_header(&line.text) || line.text.starts_with("Active ") {
+                TIER_HEADER
+            } else {
+                tier(&line.text)
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const SS: &str = "Netid State     Recv-Q Send-Q Local Address:Port  Peer Address:Port
+tcp   LISTEN    0      128    0.0.0.0:22          0.0.0.0:*
+tcp   ESTAB     0      0      10.0.0.2:51000      140.82.112.3:443
+tcp   ESTAB     0      0      10.0.0.2:51002      10.0.0.9:6379
+tcp   TIME-WAIT 0      0      10.0.0.2:51004      140.82.112.3:443
+tcp   ESTAB     0      0      10.0.0.2:22         10.0.0.7:60123
+";
+
+    #[gtest]
+    fn sockets_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(SS), 100, 20)?;
+        let p = Sockets::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_LISTEN, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_ESTABLISHED, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_UNUSUAL, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_TIME_WAIT, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_ESTABLISHED, 5]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn netstat_lines() {
+        expect_that!(
+            tier("tcp        0      0 10.0.0.2:40000   10.0.0.9:5432   ESTABLISHED"),
+            eq(TIER_UNUSUAL)
+        );
+        expect_that!(
+            tier("tcp        0      0 0.0.0.0:80       0.0.0.0:*       LISTEN"),
+            eq(TIER_LISTEN)
+        );
+    }
+}
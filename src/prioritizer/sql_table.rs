@@ -0,0 +1,114 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_FRAME: u32 = 0;
+const TIER_ROW: u32 = 1;
+
+// mysql's `+----+------+` and psql's `----+------`
+fn is_frame(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.contains("-+") && trimmed.chars().all(|c| c == '-' || c == '+')
+}
+
+// psql's `(2 rows)`, mysql's `2 rows in set (0.00 sec)`
+fn is_footer(text: &str) -> bool {
+    let trimmed = text.trim();
+    (trimmed.starts_with('(') && (trimmed.ends_with(" rows)") || trimmed.ends_with(" row)")))
+        || trimmed.contains(" in set")
+        || trimmed.starts_with("Empty set")
+}
+
+pub struct SqlTable {
+    confidence: Confidence,
+}
+impl SqlTable {
+    pub fn new(sample_lines: &Lines) -> SqlTable {
+        let has_frame = sample_lines.lines.iter().any(|l| is_frame(&l.text));
+        let has_columns = sample_lines.lines.iter().any(|l| l.text.contains(" | "));
+        SqlTable {
+            confidence: if has_frame && has_columns {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for SqlTable {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        // the column names sit between the first two frame lines (mysql) or
+        // right above the first one (psql)
+        let header = lines
+            .lines
+            .iter()
+            .position(|l| is_frame(&l.text))
+            .and_then(|f| {
+                if lines.lines[f].text.trim_start().starts_with('+') {
+                    Some(f + 1)
+                } else {
+                    f.checked_sub(1)
+                }
+            });
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let is_header = header == Some(line_number);
+            let tier = if is_frame(&line.text) || is_footer(&line.text) || is_header {
+                TIER_FRAME
+            } else {
+                TIER_ROW
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    #[gtest]
+    fn sql_table_psql() -> Result<()> {
+        let c = Cursor::new(" id | name\n----+------\n  1 | a\n  2 | b\n(2 rows)\n");
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = SqlTable::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_FRAME, TIER_FRAME, TIER_ROW, TIER_ROW, TIER_FRAME
+            ])
+        );
+        Ok(())
+    }
+
+    #[gtest]
+    fn sql_table_mysql() -> Result<()> {
+        let c = Cursor::new(
+            "+----+------+\n| id | name |\n+----+------+\n|  1 | a    |\n|  2 | b    |\n+----+------+\n2 rows in set (0.00 sec)\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = SqlTable::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        let tiers: Vec<u32> = lines.lines.iter().map(|l| l.prio[0]).collect();
+        expect_that!(
+            tiers,
+            eq(&vec![
+                TIER_FRAME, TIER_FRAME, TIER_FRAME, TIER_ROW, TIER_ROW, TIER_FRAME, TIER_FRAME,
+            ])
+        );
+        Ok(())
+    }
+}
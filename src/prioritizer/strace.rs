@@ -0,0 +1,182 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_FAILURE: u32 = 0;
+const TIER_EVENT: u32 = 1;
+const TIER_EXEC_OPEN: u32 = 2;
+const TIER_OTHER: u32 = 3;
+const TIER_FREQUENT: u32 = 4;
+const TIER_REPEAT: u32 = 5;
+
+const EXEC_OPEN: [&str; 12] = [
+    "execve", "execveat", "open", "openat", "openat2", "connect", "bind", "clone", "clone3",
+    "fork", "vfork", "unlink",
+];
+// the loops that make up most of a trace
+const FREQUENT: [&str; 16] = [
+    "read",
+    "write",
+    "pread64",
+    "pwrite64",
+    "readv",
+    "writev",
+    "poll",
+    "ppoll",
+    "select",
+    "epoll_wait",
+    "recvfrom",
+    "sendto",
+    "futex",
+    "nanosleep",
+    "clock_gettime",
+    "lseek",
+];
+
+struct Syscall<'a> {
+    name: &'a str,
+    result: &'a str,
+}
+
+// `openat(AT_FDCWD, "/etc/passwd", O_RDONLY) = -1 ENOENT (No such file or directory)`,
+// possibly prefixed with `[pid  42] `, `42  ` and `-t`/`-tt`/`-r` timestamps.
+// strace pads the calls so that their results line up, `close(3)    = 0`
+fn syscall(text: &str) -> Option<Syscall<'_>> {
+    let mut rest = text;
+    if let Some(r) = rest.strip_prefix("[pid") {
+        rest = r.split_once(']')?.1;
+    }
+    loop {
+        rest = rest.trim_start();
+        let word = rest.split(' ').next()?;
+        if !word.is_empty()
+            && word
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == ':' || c == '.')
+        {
+            rest = &rest[word.len()..];
+        } else {
+            break;
+        }
+    }
+    let (name, _) = rest.split_once('(')?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    // the last `)` followed by `=`, results can have parentheses too
+    let result = rest
+        .match_indices(')')
+        .rev()
+        .find_map(|(i, _)| rest[i + 1..].trim_start().strip_prefix("= "))?;
+    Some(Syscall { name, result })
+}
+
+fn is_event(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    trimmed.starts_with("+++ ") || trimmed.starts_with("--- ") || trimmed.contains(" +++")
+}
+
+pub struct Strace {
+    confidence: Confidence,
+}
+impl Strace {
+    pub fn new(sample_lines: &Lines) -> Strace {
+        Strace {
+            confidence: if sample_lines.lines.len() > 1
+                && share_of_lines(sample_lines, |t| syscall(t).is_some()) >= 0.6
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Strace {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // runs of the same call end up as one line and a counted marker
+        lines.marker = Marker::Counted;
+        let mut previous_name = String::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match syscall(&line.text) {
+                Some(call) if call.result.starts_with("-1 ") => TIER_FAILURE,
+                Some(call) => {
+                    let repeated = call.name == previous_name;
+                    previous_name = call.name.to_string();
+                    if EXEC_OPEN.contains(&call.name) {
+                        TIER_EXEC_OPEN
+                    } else if repeated {
+                        TIER_REPEAT
+                    } else if FREQUENT.contains(&call.name) {
+                        TIER_FREQUENT
+                    } else {
+                        TIER_OTHER
+                    }
+                }
+                None if is_event(&line.text) => TIER_EVENT,
+                None => TIER_OTHER,
+            };
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const STRACE: &str = "execve(\"/bin/cat\", [\"cat\", \"x\"], 0x7ffd /* 20 vars */) = 0
+openat(AT_FDCWD, \"x\", O_RDONLY) = -1 ENOENT (No such file or directory)
+[pid  4242] read(3, \"abc\", 4096) = 3
+[pid  4242] read(3, \"def\", 4096) = 3
+12:00:01.000042 read(3, \"\", 4096) = 0
+fstat(1, {st_mode=S_IFCHR|0620, ...}) = 0
++++ exited with 1 +++
+";
+
+    #[gtest]
+    fn strace_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(STRACE), 100, 20)?;
+        let p = Strace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(lines.marker, eq(Marker::Counted));
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_EXEC_OPEN, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_FAILURE, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FREQUENT, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_REPEAT, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_REPEAT, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_OTHER, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_EVENT, 6]));
+        Ok(())
+    }
+
+    const PADDED: &str = "execve(\"/bin/true\", [\"true\"], 0x7ffd /* 20 vars */) = 0
+brk(NULL)                               = 0x55d0c4a2c000
+access(\"/etc/ld.so.preload\", R_OK)      = -1 ENOENT (No such file or directory)
+openat(AT_FDCWD, \"/etc/ld.so.cache\", O_RDONLY|O_CLOEXEC) = 3
+close(3)                                = 0
++++ exited with 0 +++
+";
+
+    #[gtest]
+    fn strace_padded_results() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PADDED), 100, 20)?;
+        let p = Strace::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_OTHER, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FAILURE, 2]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_OTHER, 4]));
+        Ok(())
+    }
+}
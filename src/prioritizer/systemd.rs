@@ -0,0 +1,160 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_FAILED: u32 = 1;
+const TIER_ACTIVATING: u32 = 2;
+const TIER_JOURNAL: u32 = 3;
+const TIER_INACTIVE: u32 = 4;
+const TIER_ACTIVE: u32 = 5;
+const TIER_LEGEND: u32 = 6;
+
+// status bullets, `●` is also used for failed units in listings
+const BULLETS: [&str; 4] = ["●", "○", "×", "*"];
+
+fn is_listing_header(text: &str) -> bool {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    words.starts_with(&["UNIT", "LOAD", "ACTIVE", "SUB"])
+}
+
+// `● nginx.service - A high performance web server`
+fn is_status_title(text: &str) -> bool {
+    BULLETS.iter().any(|b| text.starts_with(b)) && text.contains(" - ")
+}
+
+// `  nginx.service  loaded active running  A high performance web server`
+fn unit_tier(text: &str) -> Option<u32> {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    if words.first().is_some_and(|w| BULLETS.contains(w)) {
+        words.remove(0);
+    }
+    let (load, active) = (words.get(1)?, words.get(2)?);
+    if !words[0].contains('.') {
+        return None;
+    }
+    Some(match (*load, *active) {
+        (_, "failed") => TIER_FAILED,
+        ("not-found" | "error" | "masked" | "bad-setting", _) => TIER_ACTIVATING,
+        (_, "activating" | "deactivating" | "reloading" | "maintenance") => TIER_ACTIVATING,
+        (_, "inactive") => TIER_INACTIVE,
+        (_, "active") => TIER_ACTIVE,
+        _ => return None,
+    })
+}
+
+pub struct Systemd {
+    confidence: Confidence,
+}
+impl Systemd {
+    pub fn new(sample_lines: &Lines) -> Systemd {
+        let listing = sample_lines
+            .lines
+            .iter()
+            .any(|l| is_listing_header(&l.text));
+        let status = sample_lines
+            .lines
+            .first()
+            .is_some_and(|l| is_status_title(&l.text))
+            && sample_lines
+                .lines
+                .iter()
+                .any(|l| l.text.trim_start().starts_with("Loaded:"));
+        Systemd {
+            confidence: if listing || status {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Systemd {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // the running units are what gets collapsed, the count tells how many
+        lines.marker = Marker::Counted;
+        let len: usize = lines.lines.len();
+        let mut in_status_header = false;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            in_status_header = if is_status_title(&line.text) {
+                true
+            } else {
+                in_status_header && !line.text.trim().is_empty()
+            };
+            let tier = if is_listing_header(&line.text)
+                || in_status_header
+                || line.text.contains("units listed")
+            {
+                TIER_HEADER
+            } else if let Some(tier) = unit_tier(&line.text) {
+                tier
+            } else if line.text.trim().is_empty() || line.text.contains(" = ") {
+                TIER_LEGEND
+            } else {
+                // journal lines of `systemctl status`, the latest are the interesting ones
+                TIER_JOURNAL
+            };
+            line.prio.push(tier);
+            line.prio.push((len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const LIST_UNITS: &str = "  UNIT              LOAD   ACTIVE     SUB     DESCRIPTION
+  cron.service      loaded active     running Regular background program
+● nginx.service     loaded failed     failed  A high performance web server
+  ssh.service       loaded active     running OpenBSD Secure Shell server
+  setup.service     loaded activating start   Initial setup
+
+LOAD   = Reflects whether the unit definition was properly loaded.
+4 loaded units listed.
+";
+
+    #[gtest]
+    fn systemd_list_units() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(LIST_UNITS), 100, 20)?;
+        let p = Systemd::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(lines.marker, eq(Marker::Counted));
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 7]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ACTIVE, 6]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_FAILED, 5]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_ACTIVATING, 3]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_LEGEND, 1]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_HEADER, 0]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn systemd_status() -> Result<()> {
+        let c = Cursor::new(
+            "× nginx.service - web server
+     Loaded: loaded (/lib/systemd/system/nginx.service; enabled)
+     Active: failed (Result: exit-code) since Fri 2026-10-16 10:00:00 UTC
+
+Oct 16 10:00:00 host nginx[42]: bind() to 0.0.0.0:80 failed
+",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Systemd::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_HEADER, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_LEGEND, 1]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_JOURNAL, 0]));
+        Ok(())
+    }
+}
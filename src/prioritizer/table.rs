@@ -0,0 +1,168 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_ROW: u32 = 1;
+
+// share of the rows that must have a gap right before each column
+const CONSISTENT_ROWS: f32 = 0.9;
+// fewer columns happen by chance in lists and prose
+const MIN_COLUMNS: usize = 3;
+
+fn is_gap(chars: &[char], position: usize) -> bool {
+    chars.get(position).is_some_and(|c| *c == ' ')
+}
+
+// character positions where the header's columns start, if the rows below
+// agree with them
+pub fn column_starts(sample_lines: &Lines) -> Option<Vec<usize>> {
+    let rows: Vec<Vec<char>> = sample_lines
+        .lines
+        .iter()
+        .filter(|l| !l.text.trim().is_empty())
+        .map(|l| l.text.chars().collect())
+        .collect();
+    let (header, rows) = rows.split_first()?;
+    if rows.len() < 2 || header.contains(&'\t') {
+        return None;
+    }
+    // multi word headers (`CONTAINER ID`) have starts the rows ignore
+    let starts: Vec<usize> = (0..header.len())
+        .filter(|p| header[*p] != ' ' && (*p == 0 || header[*p - 1] == ' '))
+        .filter(|p| {
+            let n_consistent = rows.iter().filter(|r| *p == 0 || is_gap(r, *p - 1)).count();
+            n_consistent as f32 >= CONSISTENT_ROWS * rows.len() as f32
+        })
+        .collect();
+    if starts.len() >= MIN_COLUMNS {
+        Some(starts)
+    } else {
+        None
+    }
+}
+
+// columns of a header whose names are separated by at least two spaces,
+// as `(start, name)`; docker and kubectl align on the header like this
+pub fn named_columns(header: &str) -> Vec<(usize, String)> {
+    let chars: Vec<char> = header.chars().collect();
+    let mut columns: Vec<(usize, String)> = Vec::new();
+    for (position, c) in chars.iter().enumerate() {
+        let separated =
+            position == 0 || (position >= 2 && chars[position - 2..position] == [' ', ' ']);
+        if *c != ' ' && (separated || columns.is_empty()) {
+            columns.push((position, String::new()));
+        }
+        if let Some((_, name)) = columns.last_mut() {
+            name.push(*c);
+        }
+    }
+    columns
+        .into_iter()
+        .map(|(start, name)| (start, name.trim_end().to_string()))
+        .collect()
+}
+
+// the trimmed value of column `name` in `text`
+pub fn field(text: &str, columns: &[(usize, String)], name: &str) -> Option<String> {
+    let index = columns.iter().position(|(_, n)| n == name)?;
+    let start = columns[index].0;
+    let end = columns.get(index + 1).map_or(usize::MAX, |(s, _)| *s);
+    let value: String = text
+        .chars()
+        .skip(start)
+        .take(end.saturating_sub(start))
+        .collect();
+    Some(value.trim().to_string())
+}
+
+pub struct AlignedTable {
+    confidence: Confidence,
+}
+impl AlignedTable {
+    pub fn new(sample_lines: &Lines) -> AlignedTable {
+        AlignedTable {
+            // format specific table prioritizers should win over this one
+            confidence: match column_starts(sample_lines) {
+                Some(_) => Confidence::Medium,
+                None => Confidence::Low,
+            },
+        }
+    }
+}
+impl Prioritizer for AlignedTable {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let header = lines.lines.iter().position(|l| !l.text.trim().is_empty());
+        let len: usize = lines.lines.len();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            line.prio.push(if Some(line_number) == header {
+                TIER_HEADER
+            } else {
+                TIER_ROW
+            });
+            line.prio
+                .push(line_number.min(len - line_number - 1) as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const DOCKER_PS: &str = "CONTAINER ID   IMAGE     STATUS
+0123456789ab   nginx     Up 2 hours
+123456789abc   redis     Exited (1) 3 minutes ago
+23456789abcd   postgres  Up 5 days
+";
+
+    #[gtest]
+    fn aligned_table_columns() {
+        let lines = Lines::from_reader(Cursor::new(DOCKER_PS), 100, 20).unwrap();
+        expect_that!(column_starts(&lines), some(eq(&vec![0, 15, 25])));
+
+        let prose = Cursor::new("this is not\na table at all\nbut some prose\n");
+        let lines = Lines::from_reader(prose, 100, 20).unwrap();
+        expect_that!(column_starts(&lines), none());
+    }
+
+    #[gtest]
+    fn aligned_table_named_columns() {
+        let columns = named_columns(DOCKER_PS.lines().next().unwrap());
+        expect_that!(
+            columns,
+            elements_are![
+                eq(&(0, String::from("CONTAINER ID"))),
+                eq(&(15, String::from("IMAGE"))),
+                eq(&(25, String::from("STATUS")))
+            ]
+        );
+        let row = "123456789abc   redis     Exited (1) 3 minutes ago";
+        expect_that!(field(row, &columns, "IMAGE"), some(eq("redis")));
+        expect_that!(
+            field(row, &columns, "STATUS"),
+            some(eq("Exited (1) 3 minutes ago"))
+        );
+        expect_that!(field(row, &columns, "PORTS"), none());
+    }
+
+    #[gtest]
+    fn aligned_table_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(DOCKER_PS), 100, 20)?;
+        let p = AlignedTable::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(20));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_ROW, 1]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_ROW, 0]));
+        Ok(())
+    }
+}
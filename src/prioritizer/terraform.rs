@@ -0,0 +1,140 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::Lines;
+use anyhow::Result;
+
+const TIER_SUMMARY: u32 = 0;
+const TIER_CHANGE_HEADER: u32 = 1;
+const TIER_RESOURCE: u32 = 2;
+const TIER_CHANGED: u32 = 3;
+const TIER_OTHER: u32 = 4;
+const TIER_UNCHANGED: u32 = 5;
+
+fn is_summary(text: &str) -> bool {
+    let trimmed = text.trim_start_matches([' ', '│', '╷', '╵']);
+    trimmed.starts_with("Plan: ")
+        || trimmed.starts_with("No changes.")
+        || trimmed.starts_with("Error: ")
+        || trimmed.starts_with("Warning: ")
+        || trimmed.starts_with("Changes to Outputs:")
+}
+
+// `  # aws_instance.web will be updated in-place`, `... must be replaced`
+fn is_change_header(text: &str) -> bool {
+    text.trim_start()
+        .strip_prefix("# ")
+        .is_some_and(|t| t.contains(" will be ") || t.contains(" must be "))
+}
+
+// `  ~ resource "aws_instance" "web" {`
+fn is_resource(text: &str) -> bool {
+    let trimmed = text.trim_start_matches([' ', '+', '-', '~', '/', '<', '=']);
+    trimmed.starts_with("resource \"") || trimmed.starts_with("data \"")
+}
+
+fn is_changed(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    ["+ ", "- ", "~ ", "-/+ ", "+/- "]
+        .iter()
+        .any(|m| trimmed.starts_with(m))
+}
+
+pub struct Terraform {
+    confidence: Confidence,
+}
+impl Terraform {
+    pub fn new(sample_lines: &Lines) -> Terraform {
+        Terraform {
+            confidence: if sample_lines.lines.iter().any(|l| {
+                l.text
+                    .starts_with("Terraform will perform the following actions")
+                    || l.text.starts_with("Plan: ")
+                    || l.text.contains("Terraform used the selected providers")
+            }) {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Terraform {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        // each resource shows its first changes before any shows its tenth
+        let mut index_in_resource: u32 = 0;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = if is_summary(&line.text) {
+                TIER_SUMMARY
+            } else if is_change_header(&line.text) {
+                index_in_resource = 0;
+                TIER_CHANGE_HEADER
+            } else if is_resource(&line.text) {
+                TIER_RESOURCE
+            } else if is_changed(&line.text) {
+                index_in_resource += 1;
+                TIER_CHANGED
+            } else if line.text.starts_with("    ") && line.text.trim() != "}" {
+                // unchanged attributes and `# (12 unchanged attributes hidden)`
+                TIER_UNCHANGED
+            } else {
+                TIER_OTHER
+            };
+            line.prio.push(tier);
+            line.prio.push(if tier == TIER_CHANGED {
+                index_in_resource
+            } else {
+                0
+            });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const PLAN: &str = "Terraform will perform the following actions:
+
+  # aws_instance.web will be updated in-place
+  ~ resource \"aws_instance\" \"web\" {
+        id            = \"i-0123456789\"
+      ~ instance_type = \"t2.micro\" -> \"t3.micro\"
+      ~ monitoring    = false -> true
+        # (28 unchanged attributes hidden)
+    }
+
+  # aws_s3_bucket.logs will be destroyed
+  - resource \"aws_s3_bucket\" \"logs\" {
+      - bucket = \"logs\" -> null
+    }
+
+Plan: 0 to add, 1 to change, 1 to destroy.
+";
+
+    #[gtest]
+    fn terraform_plan() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(PLAN), 100, 20)?;
+        let p = Terraform::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_OTHER, 0, 0]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_CHANGE_HEADER, 0, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_RESOURCE, 0, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_UNCHANGED, 0, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_CHANGED, 1, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_CHANGED, 2, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![TIER_UNCHANGED, 0, 7]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![TIER_OTHER, 0, 8]));
+        expect_that!(&lines.lines[12].prio, eq(&vec![TIER_CHANGED, 1, 12]));
+        expect_that!(&lines.lines[15].prio, eq(&vec![TIER_SUMMARY, 0, 15]));
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+// timestamps in the formats logs commonly use, as seconds that compare the way
+// the instants do within one input
+
+pub const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// days since 1970-01-01 of a proleptic gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn number(s: &str, digits: usize) -> Option<u32> {
+    let n = s.get(..digits)?;
+    if n.chars().all(|c| c.is_ascii_digit()) {
+        n.parse().ok()
+    } else {
+        None
+    }
+}
+
+// `HH:MM:SS`
+fn parse_time(s: &str) -> Option<u32> {
+    if s.get(2..3)? != ":" || s.get(5..6)? != ":" {
+        return None;
+    }
+    Some(number(s, 2)? * 3600 + number(&s[3..], 2)? * 60 + number(&s[6..], 2)?)
+}
+
+// seconds east of UTC of `Z`, `+02:00`, `-0500`, nothing counts as UTC
+fn parse_offset(s: &str) -> i64 {
+    let sign = match s.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return 0,
+    };
+    let s = s[1..].replacen(':', "", 1);
+    match (number(&s, 2), s.get(2..4).and_then(|m| number(m, 2))) {
+        (Some(hours), Some(minutes)) => sign * (hours * 3600 + minutes * 60) as i64,
+        _ => 0,
+    }
+}
+
+// `2025-06-01T10:00:00`, `2025-06-01 10:00:00`, and RFC 3339's
+// `2025-06-01T10:00:00.123+02:00`
+fn parse_iso(s: &str) -> Option<u64> {
+    if s.get(4..5)? != "-" || s.get(7..8)? != "-" || !matches!(s.get(10..11)?, "T" | " ") {
+        return None;
+    }
+    let days = days_from_civil(
+        number(s, 4)? as i64,
+        number(&s[5..], 2)?,
+        number(&s[8..], 2)?,
+    );
+    let zone = s
+        .get(19..)
+        .unwrap_or("")
+        .trim_start_matches(|c: char| c == '.' || c == ',' || c.is_ascii_digit());
+    let seconds = days * 86400 + parse_time(&s[11..])? as i64 - parse_offset(zone);
+    u64::try_from(seconds).ok()
+}
+
+// `Jun  1 10:00:00`, the year is unknown so only the order within a year counts
+fn parse_syslog(s: &str) -> Option<u64> {
+    let month = MONTHS.iter().position(|m| s.starts_with(m))? as u64;
+    let rest = s.get(3..)?.trim_start();
+    let (day, rest) = rest.split_once(' ')?;
+    let day: u64 = day.parse().ok()?;
+    Some((month * 31 + day) * 86400 + parse_time(rest)? as u64)
+}
+
+// `16/Oct/2026:10:00:00 +0000`, the common log format of nginx and apache
+fn parse_common_log(s: &str) -> Option<u64> {
+    let (day, rest) = s.split_once('/')?;
+    let (month, rest) = rest.split_once('/')?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let days = days_from_civil(number(rest, 4)? as i64, month, day.parse().ok()?);
+    let rest = rest.get(4..)?.strip_prefix(':')?;
+    let seconds =
+        days * 86400 + parse_time(rest)? as i64 - parse_offset(rest.get(8..)?.trim_start());
+    u64::try_from(seconds).ok()
+}
+
+// unix time in seconds or milliseconds, possibly with a fraction
+fn parse_epoch(s: &str) -> Option<u64> {
+    let digits: &str = s.split(|c: char| !c.is_ascii_digit()).next()?;
+    match digits.len() {
+        10 => digits.parse().ok(),
+        13 => digits.parse::<u64>().ok().map(|ms| ms / 1000),
+        _ => None,
+    }
+}
+
+fn parse_timestamp(s: &str) -> Option<u64> {
+    parse_iso(s)
+        .or_else(|| parse_syslog(s))
+        .or_else(|| parse_common_log(s))
+        .or_else(|| parse_epoch(s))
+}
+
+// seconds of the timestamp the line starts with, comparable within one input
+pub fn leading_timestamp(text: &str) -> Option<u64> {
+    parse_timestamp(text.trim_start().trim_start_matches('['))
+}
+
+// also finds timestamps in brackets further in the line, as access logs have
+// them after the client address
+pub fn find_timestamp(text: &str) -> Option<u64> {
+    leading_timestamp(text).or_else(|| {
+        text.match_indices('[')
+            .find_map(|(i, _)| parse_timestamp(&text[i + 1..]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn timestamp_formats() {
+        expect_that!(
+            leading_timestamp("2025-06-01T10:00:00Z x"),
+            some(eq(1748772000))
+        );
+        expect_that!(
+            leading_timestamp("[2025-06-01 10:00:01] x"),
+            some(eq(1748772001))
+        );
+        expect_that!(leading_timestamp("1748772000.123 x"), some(eq(1748772000)));
+        expect_that!(leading_timestamp("1748772000123 x"), some(eq(1748772000)));
+        expect_that!(
+            leading_timestamp("Jun  1 10:00:00 host app: x"),
+            lt(leading_timestamp("Jun 12 09:00:00 host app: x"))
+        );
+        expect_that!(leading_timestamp("12 apples"), none());
+        expect_that!(leading_timestamp("2025-06-01T10:00"), none());
+    }
+
+    #[gtest]
+    fn timestamp_zones_and_access_logs() {
+        expect_that!(
+            leading_timestamp("2025-06-01T12:00:00.250+02:00 x"),
+            some(eq(1748772000))
+        );
+        expect_that!(
+            leading_timestamp("2025-06-01T05:30:00-0430 x"),
+            some(eq(1748772000))
+        );
+        expect_that!(
+            find_timestamp("10.0.0.1 - - [01/Jun/2025:10:00:00 +0000] \"GET / HTTP/1.1\" 200"),
+            some(eq(1748772000))
+        );
+        expect_that!(find_timestamp("see [1] and [2]"), none());
+    }
+}
@@ -0,0 +1,149 @@
+use super::{Confidence, Prioritizer};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+const TIER_HEADER: u32 = 0;
+const TIER_EVENTFUL: u32 = 1;
+const TIER_HOP: u32 = 2;
+
+// milliseconds added by a single hop that are worth a look
+const LATENCY_JUMP_MS: f64 = 20.0;
+
+fn is_header(text: &str) -> bool {
+    text.starts_with("traceroute to ")
+        || text.starts_with("traceroute6 to ")
+        || text.starts_with("HOST: ")
+        || text.starts_with("Start: ")
+}
+
+struct Hop {
+    unanswered: bool,
+    latency: Option<f64>,
+    loss: f64,
+}
+
+// traceroute's ` 3  10.0.0.1 (10.0.0.1)  8.1 ms  8.3 ms  * ` and mtr's
+// `  2.|-- 10.0.0.1   0.0%  10  8.1  8.2  8.0  8.9  0.3`
+fn hop(text: &str) -> Option<Hop> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let number = words.first()?.trim_end_matches("|--").trim_end_matches('.');
+    number.parse::<u32>().ok()?;
+    if let Some(loss) = words.get(2).and_then(|w| w.strip_suffix('%')) {
+        let loss: f64 = loss.parse().ok()?;
+        return Some(Hop {
+            unanswered: loss >= 100.0,
+            latency: words.get(5).and_then(|w| w.parse().ok()),
+            loss,
+        });
+    }
+    let times: Vec<f64> = words
+        .windows(2)
+        .filter(|w| w[1] == "ms")
+        .filter_map(|w| w[0].parse().ok())
+        .collect();
+    let n_stars = words.iter().filter(|w| **w == "*").count();
+    Some(Hop {
+        unanswered: times.is_empty(),
+        latency: (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64),
+        loss: if n_stars > 0 { 1.0 } else { 0.0 },
+    })
+}
+
+pub struct Traceroute {
+    confidence: Confidence,
+}
+impl Traceroute {
+    pub fn new(sample_lines: &Lines) -> Traceroute {
+        Traceroute {
+            confidence: match sample_lines.lines.first() {
+                Some(l) if is_header(&l.text) => Confidence::Certain,
+                _ => Confidence::Low,
+            },
+        }
+    }
+}
+impl Prioritizer for Traceroute {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Counted;
+        let hops: Vec<Option<Hop>> = lines.lines.iter().map(|l| hop(&l.text)).collect();
+        let first_hop = hops.iter().position(|h| h.is_some());
+        let last_hop = hops.iter().rposition(|h| h.is_some());
+        let mut previous_latency: Option<f64> = None;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let tier = match &hops[line_number] {
+                None => TIER_HEADER,
+                // the destination, or how far it got
+                Some(_) if Some(line_number) == first_hop || Some(line_number) == last_hop => {
+                    TIER_HEADER
+                }
+                Some(hop) => {
+                    let jump = matches!((previous_latency, hop.latency),
+                        (Some(p), Some(l)) if l - p >= LATENCY_JUMP_MS);
+                    if hop.unanswered || hop.loss > 0.0 || jump {
+                        TIER_EVENTFUL
+                    } else {
+                        TIER_HOP
+                    }
+                }
+            };
+            if let Some(latency) = hops[line_number].as_ref().and_then(|h| h.latency) {
+                previous_latency = Some(latency);
+            }
+            line.prio.push(tier);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TRACEROUTE: &str =
+        "traceroute to example.com (93.184.216.34), 30 hops max, 60 byte packets
+ 1  _gateway (192.168.1.1)  1.234 ms  1.100 ms  1.050 ms
+ 2  * * *
+ 3  10.0.0.1 (10.0.0.1)  8.1 ms  8.3 ms  8.0 ms
+ 4  10.0.1.1 (10.0.1.1)  9.1 ms  9.3 ms  9.0 ms
+ 5  ae-1.r20.ntt.net (129.250.2.1)  95.3 ms  95.1 ms  95.0 ms
+ 6  93.184.216.34 (93.184.216.34)  96.0 ms  96.2 ms  96.1 ms
+";
+
+    #[gtest]
+    fn traceroute_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TRACEROUTE), 100, 20)?;
+        let p = Traceroute::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![TIER_HEADER, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![TIER_HEADER, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![TIER_EVENTFUL, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_HOP, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_HOP, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![TIER_EVENTFUL, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![TIER_HEADER, 6]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn traceroute_mtr() -> Result<()> {
+        let c = Cursor::new(
+            "Start: 2026-10-16T10:00:00+0000\nHOST: box    Loss%   Snt   Last   Avg  Best  Wrst StDev\n  1.|-- _gateway   0.0%    10    1.2   1.1   1.0   1.5   0.1\n  2.|-- 10.0.0.1  30.0%    10    8.2   8.1   8.0   8.9   0.3\n  3.|-- 10.0.1.1   0.0%    10    9.2   9.1   9.0   9.9   0.3\n  4.|-- 1.1.1.1    0.0%    10    9.5   9.4   9.0   9.9   0.3\n",
+        );
+        let mut lines = Lines::from_reader(c, 100, 20)?;
+        let p = Traceroute::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[3].prio, eq(&vec![TIER_EVENTFUL, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![TIER_HOP, 4]));
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::{Lines, Marker};
+use anyhow::Result;
+
+// every level of `tree` output is four columns wide: `│   `, `├── `, `└── `
+// (or `|   `, `|-- `, `` `-- `` with --charset=ascii)
+const LEVEL_WIDTH: usize = 4;
+
+fn is_branch(text: &str) -> bool {
+    ["├── ", "└── ", "|-- ", "`-- "]
+        .iter()
+        .any(|b| text.contains(b))
+}
+
+fn depth(text: &str) -> u32 {
+    let prefix = text
+        .chars()
+        .take_while(|c| c.is_whitespace() || "│├└─|`-".contains(*c))
+        .count();
+    prefix.div_ceil(LEVEL_WIDTH) as u32
+}
+
+// `12 directories, 34 files`
+fn is_summary(text: &str) -> bool {
+    let mut words = text.split_whitespace();
+    words.next().is_some_and(|n| n.parse::<u32>().is_ok())
+        && words
+            .next()
+            .is_some_and(|w| w.starts_with("director") || w.starts_with("file"))
+}
+
+pub struct Tree {
+    confidence: Confidence,
+}
+impl Tree {
+    pub fn new(sample_lines: &Lines) -> Tree {
+        Tree {
+            confidence: if sample_lines.lines.len() > 2
+                && share_of_lines(sample_lines, is_branch) >= 0.5
+            {
+                Confidence::Certain
+            } else {
+                Confidence::Low
+            },
+        }
+    }
+}
+impl Prioritizer for Tree {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        lines.marker = Marker::Indented;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            // the root has no branch, so it sits at depth 0 with the summary
+            let depth = if is_summary(&line.text) {
+                0
+            } else if line.text.trim().is_empty() {
+                u32::MAX
+            } else {
+                depth(&line.text)
+            };
+            line.prio.push(depth);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const TREE: &str = ".
+├── src
+│   ├── lines.rs
+│   └── prioritizer
+│       └── tree.rs
+└── README.md
+
+2 directories, 3 files
+";
+
+    #[gtest]
+    fn tree_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(TREE), 100, 20)?;
+        let p = Tree::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(lines.marker, eq(Marker::Indented));
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![1, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![2, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![3, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![1, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![u32::MAX, 6]));
+        expect_that!(&lines.lines[7].prio, eq(&vec![0, 7]));
+        Ok(())
+    }
+}
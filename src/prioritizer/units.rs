@@ -0,0 +1,123 @@
+// human readable sizes and durations, turned into numbers that compare the
+// way the quantities do
+
+// sizes are in bytes, powers of 1024 whatever the spelling
+const SIZE_UNITS: [(&str, i32); 6] = [("", 0), ("K", 1), ("M", 2), ("G", 3), ("T", 4), ("P", 5)];
+
+// durations are in seconds
+const DURATION_UNITS: [(&str, f64); 12] = [
+    ("ns", 1e-9),
+    ("us", 1e-6),
+    ("µs", 1e-6),
+    ("ms", 1e-3),
+    ("s", 1.0),
+    ("sec", 1.0),
+    ("m", 60.0),
+    ("min", 60.0),
+    ("h", 3600.0),
+    ("hr", 3600.0),
+    ("d", 86400.0),
+    ("w", 604800.0),
+];
+
+// `1,5` is a decimal comma (du in some locales), `1,024` and `1,234,567`
+// group thousands
+pub fn parse_number(number: &str) -> Option<f64> {
+    if !number.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let groups_thousands = number
+        .split(',')
+        .skip(1)
+        .all(|g| g.len() == 3 && !g.contains('.'))
+        && number.contains(',');
+    let number = if groups_thousands {
+        number.replace(',', "")
+    } else {
+        number.replacen(',', ".", 1)
+    };
+    number.parse().ok()
+}
+
+// splits `12.5ms` into `12.5` and `ms`
+fn split_unit(s: &str) -> (&str, &str) {
+    let end = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+// `1.4G`, `532K`, `1,5M` (du -h), `13.3kB`, `1.2GB` (docker), `2.0 GiB`, `512m`
+// (top), `12`
+pub fn parse_size(size: &str) -> Option<f64> {
+    let (number, unit) = split_unit(size.trim());
+    let unit = unit.trim_start();
+    let unit = unit
+        .strip_suffix("iB")
+        .or_else(|| unit.strip_suffix('B'))
+        .unwrap_or(unit)
+        .to_ascii_uppercase();
+    let (_, power) = SIZE_UNITS.iter().find(|(u, _)| *u == unit)?;
+    Some(parse_number(number)? * 1024.0_f64.powi(*power))
+}
+
+// `12ms`, `1.5s`, `3m20s`, `2h 5m`, and the `[[dd-]hh:]mm:ss` of ps and time
+pub fn parse_duration(duration: &str) -> Option<f64> {
+    let duration = duration.trim();
+    if duration.contains(':') {
+        let (days, clock) = match duration.split_once('-') {
+            Some((days, clock)) => (days.parse::<f64>().ok()?, clock),
+            None => (0.0, duration),
+        };
+        let seconds = clock.split(':').try_fold(0.0, |total, part| {
+            Some(total * 60.0 + part.parse::<f64>().ok()?)
+        })?;
+        return Some(days * 86400.0 + seconds);
+    }
+    let mut rest = duration;
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let (number, after) = split_unit(rest);
+        let end = after
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(after.len());
+        let (unit, next) = after.split_at(end);
+        let (_, multiplier) = DURATION_UNITS.iter().find(|(u, _)| *u == unit.trim())?;
+        total += parse_number(number)? * multiplier;
+        rest = next.trim_start();
+    }
+    (!duration.is_empty()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use googletest::prelude::*;
+
+    #[gtest]
+    fn units_sizes() {
+        expect_that!(parse_size("532K"), some(eq(532.0 * 1024.0)));
+        expect_that!(parse_size("1.5M"), some(eq(1.5 * 1024.0 * 1024.0)));
+        expect_that!(parse_size("1,5M"), some(eq(1.5 * 1024.0 * 1024.0)));
+        expect_that!(parse_size("12"), some(eq(12.0)));
+        expect_that!(parse_size("big"), none());
+        expect_that!(parse_size("13.3kB"), some(eq(13.3 * 1024.0)));
+        expect_that!(parse_size("2 GiB"), some(eq(2.0 * 1024.0_f64.powi(3))));
+        expect_that!(parse_size("512m"), some(eq(512.0 * 1024.0 * 1024.0)));
+        expect_that!(parse_size("1,024"), some(eq(1024.0)));
+        expect_that!(parse_size("12ms"), none());
+    }
+
+    #[gtest]
+    fn units_durations() {
+        expect_that!(parse_duration("12ms"), some(eq(0.012)));
+        expect_that!(parse_duration("1.5s"), some(eq(1.5)));
+        expect_that!(parse_duration("3m20s"), some(eq(200.0)));
+        expect_that!(parse_duration("2h 5m"), some(eq(7500.0)));
+        expect_that!(parse_duration("01:02:03"), some(eq(3723.0)));
+        expect_that!(parse_duration("1-00:00:10"), some(eq(86410.0)));
+        expect_that!(parse_duration("12"), none());
+        expect_that!(parse_duration(""), none());
+        expect_that!(parse_duration("5 apples"), none());
+    }
+}
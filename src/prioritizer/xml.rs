@@ -0,0 +1,123 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+// net change of the nesting depth over a line: `<a>` opens, `</a>` closes,
+// `<a/>`, `<?xml ...?>`, `<!-- -->` and `<!DOCTYPE>` do neither
+fn depth_change(text: &str) -> i32 {
+    let mut change = 0;
+    for tag in text.split('<').skip(1) {
+        let Some((inside, _)) = tag.split_once('>') else {
+            continue;
+        };
+        if inside.starts_with('/') {
+            change -= 1;
+        } else if !(inside.starts_with('?') || inside.starts_with('!') || inside.ends_with('/')) {
+            change += 1;
+        }
+    }
+    change
+}
+
+fn is_opening_tag(trimmed: &str) -> bool {
+    trimmed.starts_with('<') && !trimmed.starts_with("</")
+}
+
+pub struct Xml {
+    confidence: Confidence,
+}
+impl Xml {
+    pub fn new(sample_lines: &Lines) -> Xml {
+        let first_line = sample_lines
+            .lines
+            .iter()
+            .find(|l| !l.text.trim().is_empty())
+            .map(|l| l.text.trim_start().to_ascii_lowercase())
+            .unwrap_or_default();
+        let confidence = if first_line.starts_with("<?xml")
+            || first_line.starts_with("<!doctype")
+            || first_line.starts_with("<html")
+        {
+            Confidence::Certain
+        } else if sample_lines.lines.len() > 2
+            && share_of_lines(sample_lines, |t| t.trim_start().starts_with('<')) >= 0.7
+        {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        Xml { confidence }
+    }
+}
+impl Prioritizer for Xml {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let mut depth: i32 = 0;
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let trimmed = line.text.trim_start();
+            // a closing tag sits at the depth of its opening tag
+            let line_depth = if trimmed.starts_with("</") {
+                depth - 1
+            } else {
+                depth
+            };
+            depth = (depth + depth_change(trimmed)).max(0);
+            let score = 2 * line_depth.max(0) as u32;
+            line.prio.push(if is_opening_tag(trimmed) {
+                score
+            } else {
+                score + 1
+            });
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const POM: &str = r#"<?xml version="1.0"?>
+<project>
+  <dependencies>
+    <dependency>
+      <artifactId>junit</artifactId>
+      <scope/>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+    #[gtest]
+    fn xml_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(POM), 100, 20)?;
+        let p = Xml::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![4, 3]));
+        expect_that!(&lines.lines[4].prio, eq(&vec![6, 4]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![6, 5]));
+        expect_that!(&lines.lines[6].prio, eq(&vec![5, 6]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![1, 8]));
+        Ok(())
+    }
+
+    #[gtest]
+    fn tag_depth_changes() {
+        expect_that!(depth_change("<a>"), eq(1));
+        expect_that!(depth_change("<a>text</a>"), eq(0));
+        expect_that!(depth_change("<a/>"), eq(0));
+        expect_that!(depth_change("<!-- comment -->"), eq(0));
+        expect_that!(depth_change("</b></a>"), eq(-2));
+    }
+}
@@ -0,0 +1,166 @@
+use super::{Confidence, Prioritizer, share_of_lines};
+use crate::lines::Lines;
+use anyhow::Result;
+
+// fields that tell which object a manifest describes, (parent key, key)
+const IDENTITY_FIELDS: [(&str, &str); 5] = [
+    ("", "apiVersion"),
+    ("", "kind"),
+    ("metadata", "name"),
+    ("metadata", "namespace"),
+    ("", "name"),
+];
+
+fn indent(text: &str) -> usize {
+    text.len() - text.trim_start_matches(' ').len()
+}
+
+// `key: value`, `key:`, `- key: value`, returns the key
+fn key(text: &str) -> Option<&str> {
+    let text = text.trim_start().trim_start_matches("- ");
+    let (key, rest) = text.split_once(':')?;
+    if !(rest.is_empty() || rest.starts_with(' '))
+        || key.is_empty()
+        || key.contains(char::is_whitespace) && !key.starts_with('"')
+    {
+        return None;
+    }
+    Some(key.trim_matches('"'))
+}
+
+fn is_yaml_line(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.is_empty()
+        || trimmed == "---"
+        || trimmed.starts_with('#')
+        || trimmed.starts_with("- ")
+        || key(text).is_some()
+}
+
+pub struct Yaml {
+    confidence: Confidence,
+}
+impl Yaml {
+    pub fn new(sample_lines: &Lines) -> Yaml {
+        let first_line = sample_lines
+            .lines
+            .iter()
+            .find(|l| !l.text.trim().is_empty())
+            .map(|l| l.text.as_str())
+            .unwrap_or("");
+        let confidence = if first_line == "---"
+            || first_line.starts_with("apiVersion: ")
+            || first_line.starts_with("kind: ")
+        {
+            Confidence::Certain
+        } else if sample_lines.lines.len() > 2 && share_of_lines(sample_lines, is_yaml_line) > 0.9 {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        Yaml { confidence }
+    }
+}
+impl Prioritizer for Yaml {
+    fn prioritize(&self, lines: &mut Lines) -> Result<()> {
+        let unit = lines
+            .lines
+            .iter()
+            .map(|l| indent(&l.text))
+            .filter(|i| *i > 0)
+            .min()
+            .unwrap_or(2);
+        // keys of the enclosing mappings, with their indentation and line
+        let mut parents: Vec<(usize, String, usize)> = Vec::new();
+        for (line_number, line) in lines.lines.iter_mut().enumerate() {
+            let text = &line.text;
+            let trimmed = text.trim();
+            let indent = indent(text);
+            let depth = (indent / unit) as u32;
+            let score = if trimmed == "---" || trimmed.is_empty() && depth == 0 {
+                parents.clear();
+                0
+            } else if let Some(key) = key(text) {
+                while parents.last().is_some_and(|(i, _, _)| *i >= indent) {
+                    parents.pop();
+                }
+                let parent = parents.last().map_or("", |(_, k, _)| k.as_str());
+                line.parent = parents.last().map(|(_, _, n)| *n);
+                let is_identity = IDENTITY_FIELDS
+                    .iter()
+                    .any(|(p, k)| *k == key && *p == parent && (depth <= 1 || !p.is_empty()));
+                parents.push((indent, key.to_string(), line_number));
+                if is_identity {
+                    0
+                } else if trimmed.starts_with("- ") {
+                    // list item heads beat their sibling keys
+                    2 * depth + 1
+                } else {
+                    2 * depth + 2
+                }
+            } else if trimmed.starts_with("- ") {
+                2 * depth + 1
+            } else {
+                2 * depth + 2
+            };
+            line.prio.push(score);
+            line.prio.push(line_number as u32);
+        }
+        Ok(())
+    }
+
+    fn confidence(&self) -> Confidence {
+        self.confidence.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use googletest::prelude::*;
+    use std::io::Cursor;
+
+    const MANIFEST: &str = "apiVersion: v1
+kind: Pod
+metadata:
+  name: web
+  labels:
+    app: web
+spec:
+  containers:
+  - name: nginx
+    image: nginx:1.27
+    ports:
+    - containerPort: 80
+";
+
+    #[gtest]
+    fn yaml_prioritizer() -> Result<()> {
+        let mut lines = Lines::from_reader(Cursor::new(MANIFEST), 100, 20)?;
+        let p = Yaml::new(&lines);
+        expect_that!(u32::from(p.confidence()), eq(100));
+        p.prioritize(&mut lines)?;
+        expect_that!(&lines.lines[0].prio, eq(&vec![0, 0]));
+        expect_that!(&lines.lines[1].prio, eq(&vec![0, 1]));
+        expect_that!(&lines.lines[2].prio, eq(&vec![2, 2]));
+        expect_that!(&lines.lines[3].prio, eq(&vec![0, 3]));
+        expect_that!(&lines.lines[5].prio, eq(&vec![6, 5]));
+        expect_that!(&lines.lines[8].prio, eq(&vec![3, 8]));
+        expect_that!(&lines.lines[9].prio, eq(&vec![6, 9]));
+        // `name: web` keeps `metadata:` above it
+        expect_that!(lines.lines[3].parent, some(eq(2)));
+        expect_that!(lines.lines[5].parent, some(eq(4)));
+        expect_that!(lines.lines[0].parent, none());
+        Ok(())
+    }
+
+    #[gtest]
+    fn yaml_keys() {
+        expect_that!(key("  name: web"), some(eq("name")));
+        expect_that!(key("- name: nginx"), some(eq("name")));
+        expect_that!(key("image: nginx:1.27"), some(eq("image")));
+        expect_that!(key("http://example.com"), none());
+        expect_that!(key("some prose: here"), none());
+    }
+}